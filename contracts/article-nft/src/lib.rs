@@ -1,13 +1,14 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol, vec, Vec};
-use stellar_contract_utils::pausable::{self, Pausable, PausableError};
-use stellar_access::ownable::{self, Ownable, OwnableError};
-use stellar_macros::{only_owner, when_not_paused};
+use soroban_sdk::{contract, contractclient, contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, String, Symbol, vec, Vec};
+use stellar_contract_utils::pausable;
+use stellar_access::access_control::{self, AccessControl};
+use stellar_access::ownable::{self, Ownable};
+use stellar_macros::{default_impl, only_owner, only_role, when_not_paused};
 
 /// Minimal NFT implementation for articles
 /// Each article can be minted as a unique NFT once it reaches a tip threshold
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct NFTToken {
     pub token_id: u64,
@@ -20,63 +21,175 @@ pub struct NFTToken {
     pub tip_amount: i128,       // Amount of tips when minted
 }
 
+/// Stable error codes returned by every fallible entrypoint.
+/// Pause checks surface OZ `PausableError::EnforcedPause` (1000) instead.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NFTError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    AlreadyMinted = 4,
+    BelowThreshold = 5,
+    TokenNotFound = 6,
+    NotOwner = 7,
+    ArticleNotRegistered = 8,
+    NotArticleAuthor = 9,
+    InvalidTtlConfig = 10,
+    MintNotApproved = 11,
+    InvalidTipThreshold = 12,
+}
+
+/// Ledger TTL policy: entries whose TTL drops below `threshold` are
+/// extended to live for `extend_to` more ledgers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TtlConfig {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+// ========== EVENTS ==========
+// Events added since the original deployment are topic-prefixed with their
+// name and a schema version; mint and transfer keep their original layout.
+// `#[contractevent]` orders data fields by name, which can't reproduce that
+// layout, so those three are still published as raw tuples.
+
+#[contractevent(topics = ["mint_approved", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintApprovedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub approver: Address,
+}
+
+#[contractevent(topics = ["tip_threshold_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipThresholdUpdatedEvent {
+    pub old_threshold: i128,
+    pub new_threshold: i128,
+}
+
+#[contractevent(topics = ["upgraded", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["migrated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Admin,
     TokenCounter,
     Token(u64),                 // token_id -> NFTToken
     ArticleToken(Symbol),       // article_id -> token_id
     OwnerTokens(Address),       // owner -> Vec<token_id>
     TipThreshold,               // Minimum tips required to mint
     Paused,                     // Emergency pause state (OZ Pausable)
+    TippingContract,            // Tipping contract holding the article registry
+    TtlConfig,                  // TTL bump policy for instance and persistent entries
+    MintApprovalRequired,       // Whether mints need a mint_approver's sign-off
+    MintApproved(Symbol),       // article_id -> approved, until minted
+    SchemaVersion,              // Storage layout version, see migrate
+}
+
+/// Storage layout written by the original deployment (schema 0). Only read
+/// by the migration.
+#[derive(Clone)]
+#[contracttype]
+pub enum LegacyDataKey {
+    Admin,                      // Admin address, now the OZ owner and role admin
 }
 
 const DEFAULT_TIP_THRESHOLD: i128 = 100_000_000; // 10 XLM in stroops (~$1)
+const DAY_IN_LEDGERS: u32 = 17_280; // ~5s ledgers
+const DEFAULT_TTL_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+const DEFAULT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1;
+
+/// Article registry and tip totals exposed by the QuillTip tipping contract
+#[contractclient(name = "ArticleRegistryClient")]
+pub trait ArticleRegistry {
+    fn get_article_author(env: Env, article_id: Symbol) -> Option<Address>;
+    fn get_native_token(env: Env) -> Address;
+    fn get_article_total_tips(env: Env, article_id: Symbol, token: Address) -> i128;
+}
 
 #[contract]
 pub struct ArticleNFTContract;
 
 #[contractimpl]
 impl ArticleNFTContract {
-    /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address, tip_threshold: Option<i128>) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Contract already initialized");
+    /// Initialize the contract; `admin` becomes the contract owner
+    /// `tipping_contract` is the tipping contract whose article registry decides who may mint
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        tipping_contract: Address,
+        tip_threshold: Option<i128>,
+    ) -> Result<(), NFTError> {
+        // Not the owner: ownership can be renounced. The threshold is also
+        // checked so a schema 0 deployment can't be initialized over.
+        if env.storage().instance().has(&DataKey::TippingContract)
+            || env.storage().instance().has(&DataKey::TipThreshold)
+        {
+            return Err(NFTError::AlreadyInitialized);
         }
         
         admin.require_auth();
         
         let threshold = tip_threshold.unwrap_or(DEFAULT_TIP_THRESHOLD);
+        if threshold <= 0 {
+            return Err(NFTError::InvalidTipThreshold);
+        }
         
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        ownable::set_owner(&env, &admin);
+        access_control::set_admin(&env, &admin);
         env.storage().instance().set(&DataKey::TipThreshold, &threshold);
+        env.storage().instance().set(&DataKey::TippingContract, &tipping_contract);
         env.storage().persistent().set(&DataKey::TokenCounter, &0u64);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::TokenCounter);
+
+        Ok(())
     }
     
     /// Mint an article as NFT (only if tip threshold is met)
+    /// Only the article's registered author may mint; the native-token tip
+    /// total is read from the tipping contract
+    #[when_not_paused]
     pub fn mint_article_nft(
         env: Env,
         author: Address,
         article_id: Symbol,
-        tip_amount: i128,
         metadata_url: String,
-    ) -> u64 {
+    ) -> Result<u64, NFTError> {
         author.require_auth();
+        Self::require_registered_author(&env, &author, &article_id)?;
+        Self::take_mint_approval(&env, &article_id)?;
         
         // Check if article already minted
         if env.storage().persistent().has(&DataKey::ArticleToken(article_id.clone())) {
-            panic!("Article already minted as NFT");
+            return Err(NFTError::AlreadyMinted);
         }
         
         // Check tip threshold
+        let tip_amount = Self::article_tip_total(&env, &article_id)?;
         let threshold: i128 = env.storage()
             .instance()
             .get(&DataKey::TipThreshold)
             .unwrap_or(DEFAULT_TIP_THRESHOLD);
             
         if tip_amount < threshold {
-            panic!("Tip amount below threshold for minting");
+            return Err(NFTError::BelowThreshold);
         }
         
         // Generate token ID
@@ -112,34 +225,37 @@ impl ArticleNFTContract {
         
         // Update counter
         env.storage().persistent().set(&DataKey::TokenCounter, &token_id);
+        Self::extend_minted(&env, token_id, &article_id, &author);
         
         // Emit event
+        #[allow(deprecated)] // Legacy layout the indexer reads, see EVENTS
         env.events().publish(
             (Symbol::new(&env, "mint"), article_id),
             (author, token_id, tip_amount)
         );
         
-        token_id
+        Ok(token_id)
     }
     
     /// Transfer NFT ownership
+    #[when_not_paused]
     pub fn transfer(
         env: Env,
         from: Address,
         to: Address,
         token_id: u64,
-    ) {
+    ) -> Result<(), NFTError> {
         from.require_auth();
         
         // Get token
         let mut nft: NFTToken = env.storage()
             .persistent()
             .get(&DataKey::Token(token_id))
-            .expect("Token does not exist");
+            .ok_or(NFTError::TokenNotFound)?;
         
         // Verify ownership
         if nft.owner != from {
-            panic!("Not the owner of this token");
+            return Err(NFTError::NotOwner);
         }
         
         // Update owner
@@ -171,21 +287,32 @@ impl ArticleNFTContract {
             .unwrap_or(vec![&env]);
         to_tokens.push_back(token_id);
         env.storage().persistent().set(&DataKey::OwnerTokens(to.clone()), &to_tokens);
+
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::Token(token_id));
+        Self::extend_persistent(&env, &DataKey::OwnerTokens(from.clone()));
+        Self::extend_persistent(&env, &DataKey::OwnerTokens(to.clone()));
         
         // Emit event
+        #[allow(deprecated)] // Legacy layout the indexer reads, see EVENTS
         env.events().publish(
             (Symbol::new(&env, "transfer"), token_id),
             (from, to, nft.article_id)
         );
+
+        Ok(())
     }
     
-    /// Get token owner
-    pub fn get_owner(env: Env, token_id: u64) -> Address {
+    /// Get token owner; extends the token's TTL
+    /// (`get_owner` is the contract owner, from Ownable)
+    pub fn owner_of(env: Env, token_id: u64) -> Result<Address, NFTError> {
         let nft: NFTToken = env.storage()
             .persistent()
             .get(&DataKey::Token(token_id))
-            .expect("Token does not exist");
-        nft.owner
+            .ok_or(NFTError::TokenNotFound)?;
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::Token(token_id));
+        Ok(nft.owner)
     }
     
     /// Check if article is already minted
@@ -193,117 +320,261 @@ impl ArticleNFTContract {
         env.storage().persistent().has(&DataKey::ArticleToken(article_id))
     }
     
-    /// Get token by article ID
+    /// Get token by article ID; extends the token's TTL
     pub fn get_token_by_article(env: Env, article_id: Symbol) -> Option<NFTToken> {
         if let Some(token_id) = env.storage()
             .persistent()
-            .get::<DataKey, u64>(&DataKey::ArticleToken(article_id)) {
+            .get::<DataKey, u64>(&DataKey::ArticleToken(article_id.clone())) {
+            Self::extend_instance(&env);
+            Self::extend_persistent(&env, &DataKey::ArticleToken(article_id));
+            Self::extend_persistent(&env, &DataKey::Token(token_id));
             env.storage().persistent().get(&DataKey::Token(token_id))
         } else {
             None
         }
     }
     
-    /// Get all tokens owned by address
+    /// Get all tokens owned by address; extends the list's TTL
     pub fn get_owned_tokens(env: Env, owner: Address) -> Vec<u64> {
+        let key = DataKey::OwnerTokens(owner);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &key);
         env.storage()
             .persistent()
-            .get(&DataKey::OwnerTokens(owner))
+            .get(&key)
             .unwrap_or(vec![&env])
     }
     
-    /// Get tip threshold for minting
+    /// Get the tipping contract used for article registry lookups
+    pub fn get_tipping_contract(env: Env) -> Result<Address, NFTError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TippingContract)
+            .ok_or(NFTError::NotInitialized)
+    }
+
+    /// Point mint checks at a tipping contract's article registry (owner only)
+    /// Deployments from before the registry have none until this is called
+    #[only_owner]
+    pub fn set_tipping_contract(env: Env, tipping_contract: Address) -> Result<(), NFTError> {
+        env.storage().instance().set(&DataKey::TippingContract, &tipping_contract);
+        Self::extend_instance(&env);
+
+        Ok(())
+    }
+
+    /// Get tip threshold for minting; extends the instance TTL
     pub fn get_tip_threshold(env: Env) -> i128 {
+        Self::extend_instance(&env);
         env.storage()
             .instance()
             .get(&DataKey::TipThreshold)
             .unwrap_or(DEFAULT_TIP_THRESHOLD)
     }
     
-    /// Update tip threshold (admin only)
-    pub fn set_tip_threshold(env: Env, admin: Address, new_threshold: i128) {
-        admin.require_auth();
+    /// Update tip threshold (fee_manager role); must be positive
+    #[only_role(caller, "fee_manager")]
+    pub fn set_tip_threshold(env: Env, caller: Address, new_threshold: i128) -> Result<(), NFTError> {
+        if new_threshold <= 0 {
+            return Err(NFTError::InvalidTipThreshold);
+        }
+
+        let old_threshold = Self::get_tip_threshold(env.clone());
+        env.storage().instance().set(&DataKey::TipThreshold, &new_threshold);
+        Self::extend_instance(&env);
+
+        TipThresholdUpdatedEvent { old_threshold, new_threshold }.publish(&env);
 
-        let stored_admin: Address = env.storage()
+        Ok(())
+    }
+
+    // ========== MINT APPROVAL ==========
+
+    /// Check if mints need a mint_approver's sign-off
+    pub fn is_mint_approval_required(env: Env) -> bool {
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Contract not initialized");
+            .get(&DataKey::MintApprovalRequired)
+            .unwrap_or(false)
+    }
 
-        if admin != stored_admin {
-            panic!("Only admin can update threshold");
-        }
+    /// Require or stop requiring mint approval (owner only)
+    #[only_owner]
+    pub fn set_mint_approval_required(env: Env, required: bool) -> Result<(), NFTError> {
+        env.storage().instance().set(&DataKey::MintApprovalRequired, &required);
+        Self::extend_instance(&env);
 
-        env.storage().instance().set(&DataKey::TipThreshold, &new_threshold);
+        Ok(())
     }
 
-    // ========== PAUSABLE PATTERN (OZ) ==========
+    /// Approve an article for minting (mint_approver role)
+    /// The approval is used up by the mint
+    #[only_role(approver, "mint_approver")]
+    pub fn approve_mint(env: Env, approver: Address, article_id: Symbol) -> Result<(), NFTError> {
+        let key = DataKey::MintApproved(article_id.clone());
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
 
-    /// Check if contract is paused
-    pub fn is_paused(env: Env) -> bool {
-        pausable::paused(&env)
+        MintApprovedEvent { article_id, approver }.publish(&env);
+
+        Ok(())
     }
 
-    /// Pause the contract (admin only)
-    pub fn pause(env: Env, admin: Address) {
-        admin.require_auth();
+    /// Check if an article has an unused mint approval
+    pub fn is_mint_approved(env: Env, article_id: Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::MintApproved(article_id))
+    }
+
+    // ========== UPGRADES ==========
 
-        let stored_admin: Address = env.storage()
+    /// Get the storage layout version (0 for the original deployment)
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
 
-        if admin != stored_admin {
-            panic!("Unauthorized");
+    /// Replace the contract code with an uploaded wasm (owner only)
+    /// Call migrate afterwards if the new code bumps SCHEMA_VERSION
+    #[only_owner]
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), NFTError> {
+        UpgradedEvent { wasm_hash: new_wasm_hash.clone() }.publish(&env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to SCHEMA_VERSION; returns the version
+    /// Permissionless and idempotent
+    pub fn migrate(env: Env) -> Result<u32, NFTError> {
+        if !env.storage().instance().has(&DataKey::TipThreshold) {
+            return Err(NFTError::NotInitialized);
         }
 
-        pausable::pause(&env);
+        let from_version = Self::get_schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Ok(from_version);
+        }
+
+        // The legacy Admin key encodes the same as the access-control admin
+        // slot, so the old admin already holds that role; only ownership moves.
+        if let Some(admin) = env.storage().instance().get::<_, Address>(&LegacyDataKey::Admin) {
+            if ownable::get_owner(&env).is_none() {
+                ownable::set_owner(&env, &admin);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Self::extend_instance(&env);
+
+        MigratedEvent { from_version, to_version: SCHEMA_VERSION }.publish(&env);
+
+        Ok(SCHEMA_VERSION)
     }
 
-    /// Unpause the contract (admin only)
-    pub fn unpause(env: Env, admin: Address) {
-        admin.require_auth();
+    // ========== STORAGE TTL ==========
+
+    /// Extend the TTL of a token, its article lookup and its owner's token list
+    /// Permissionless so a keeper can keep minted NFTs from being archived
+    pub fn bump_token(env: Env, token_id: u64) -> Result<(), NFTError> {
+        let nft: NFTToken = env.storage()
+            .persistent()
+            .get(&DataKey::Token(token_id))
+            .ok_or(NFTError::TokenNotFound)?;
+
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::Token(token_id));
+        Self::extend_persistent(&env, &DataKey::ArticleToken(nft.article_id));
+        Self::extend_persistent(&env, &DataKey::OwnerTokens(nft.owner));
+        Self::extend_persistent(&env, &DataKey::TokenCounter);
+
+        Ok(())
+    }
 
-        let stored_admin: Address = env.storage()
+    /// Get the TTL bump policy
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                threshold: DEFAULT_TTL_THRESHOLD,
+                extend_to: DEFAULT_TTL_EXTEND_TO,
+            })
+    }
 
-        if admin != stored_admin {
-            panic!("Unauthorized");
+    /// Update the TTL bump policy (owner only)
+    #[only_owner]
+    pub fn set_ttl_config(env: Env, threshold: u32, extend_to: u32) -> Result<(), NFTError> {
+        if threshold >= extend_to || extend_to > env.storage().max_ttl() {
+            return Err(NFTError::InvalidTtlConfig);
         }
 
+        env.storage()
+            .instance()
+            .set(&DataKey::TtlConfig, &TtlConfig { threshold, extend_to });
+        Self::extend_instance(&env);
+
+        Ok(())
+    }
+
+    // ========== PAUSABLE PATTERN (OZ) ==========
+    // Pausing blocks minting and transfers (#[when_not_paused]). Owner and
+    // role settings, mint approvals, upgrade, migrate and bump_token stay
+    // open so the contract can be repaired while paused.
+
+    /// Check if contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        pausable::paused(&env)
+    }
+
+    /// Pause the contract (pauser role)
+    #[only_role(caller, "pauser")]
+    pub fn pause(env: Env, caller: Address) -> Result<(), NFTError> {
+        pausable::pause(&env);
+
+        Ok(())
+    }
+
+    /// Unpause the contract (pauser role)
+    #[only_role(caller, "pauser")]
+    pub fn unpause(env: Env, caller: Address) -> Result<(), NFTError> {
         pausable::unpause(&env);
+
+        Ok(())
     }
 
     // ========== ARWEAVE-ENABLED MINTING ==========
 
     /// Mint an article as NFT with Arweave reference
+    #[when_not_paused]
     pub fn mint_article_nft_with_arweave(
         env: Env,
         author: Address,
         article_id: Symbol,
-        tip_amount: i128,
         metadata_url: String,
         arweave_tx_id: String,
-    ) -> u64 {
-        // Check not paused
-        pausable::when_not_paused(&env);
-
+    ) -> Result<u64, NFTError> {
         author.require_auth();
+        Self::require_registered_author(&env, &author, &article_id)?;
+        Self::take_mint_approval(&env, &article_id)?;
 
         // Check if article already minted
         if env.storage().persistent().has(&DataKey::ArticleToken(article_id.clone())) {
-            panic!("Article already minted as NFT");
+            return Err(NFTError::AlreadyMinted);
         }
 
         // Check tip threshold
+        let tip_amount = Self::article_tip_total(&env, &article_id)?;
         let threshold: i128 = env.storage()
             .instance()
             .get(&DataKey::TipThreshold)
             .unwrap_or(DEFAULT_TIP_THRESHOLD);
 
         if tip_amount < threshold {
-            panic!("Tip amount below threshold for minting");
+            return Err(NFTError::BelowThreshold);
         }
 
         // Generate token ID
@@ -339,21 +610,192 @@ impl ArticleNFTContract {
 
         // Update counter
         env.storage().persistent().set(&DataKey::TokenCounter, &token_id);
+        Self::extend_minted(&env, token_id, &article_id, &author);
 
         // Emit event with Arweave TX ID
+        #[allow(deprecated)] // Legacy layout the indexer reads, see EVENTS
         env.events().publish(
             (Symbol::new(&env, "mint_arweave"), article_id),
             (author, token_id, tip_amount, arweave_tx_id)
         );
 
-        token_id
+        Ok(token_id)
+    }
+}
+
+// ========== OWNERSHIP (OZ) ==========
+// get_owner, two-step transfer_ownership/accept_ownership (the offer
+// expires at `live_until_ledger`) and renounce_ownership. The role admin
+// follows ownership: each step also runs the matching access-control admin
+// transfer, and the standalone admin transfer entrypoints are disabled.
+// After renouncing, roles can no longer be granted or revoked.
+
+#[default_impl]
+#[contractimpl]
+impl Ownable for ArticleNFTContract {
+    fn transfer_ownership(e: &Env, new_owner: Address, live_until_ledger: u32) {
+        ownable::transfer_ownership(e, &new_owner, live_until_ledger);
+        access_control::transfer_admin_role(e, &new_owner, live_until_ledger);
+    }
+
+    fn accept_ownership(e: &Env) {
+        ownable::accept_ownership(e);
+        access_control::accept_admin_transfer(e);
+    }
+
+    fn renounce_ownership(e: &Env) {
+        ownable::renounce_ownership(e);
+        access_control::renounce_admin(e);
+    }
+}
+
+// ========== ROLES (OZ) ==========
+// "pauser" pauses and unpauses, "fee_manager" sets the tip threshold and
+// "mint_approver" approves mints when approval is required. The role
+// admin (always the owner, see OWNERSHIP) grants them; none are held by
+// default.
+
+#[default_impl]
+#[contractimpl]
+impl AccessControl for ArticleNFTContract {
+    // The role admin only moves through the ownership calls above
+    fn transfer_admin_role(e: &Env, _new_admin: Address, _live_until_ledger: u32) {
+        panic_with_error!(e, NFTError::Unauthorized);
+    }
+
+    fn accept_admin_transfer(e: &Env) {
+        panic_with_error!(e, NFTError::Unauthorized);
+    }
+
+    fn renounce_admin(e: &Env) {
+        panic_with_error!(e, NFTError::Unauthorized);
+    }
+}
+
+impl ArticleNFTContract {
+    /// Check `author` is the address registered for `article_id` in the tipping contract
+    fn require_registered_author(env: &Env, author: &Address, article_id: &Symbol) -> Result<(), NFTError> {
+        let tipping_contract = Self::get_tipping_contract(env.clone())?;
+        let registered_author = ArticleRegistryClient::new(env, &tipping_contract)
+            .get_article_author(article_id)
+            .ok_or(NFTError::ArticleNotRegistered)?;
+
+        if registered_author != *author {
+            return Err(NFTError::NotArticleAuthor);
+        }
+
+        Ok(())
+    }
+
+    /// Native-token tips the tipping contract has recorded for `article_id`
+    fn article_tip_total(env: &Env, article_id: &Symbol) -> Result<i128, NFTError> {
+        let tipping_contract = Self::get_tipping_contract(env.clone())?;
+        let registry = ArticleRegistryClient::new(env, &tipping_contract);
+        Ok(registry.get_article_total_tips(article_id, &registry.get_native_token()))
+    }
+
+    /// Consume the article's mint approval, if mints need one
+    fn take_mint_approval(env: &Env, article_id: &Symbol) -> Result<(), NFTError> {
+        if !Self::is_mint_approval_required(env.clone()) {
+            return Ok(());
+        }
+
+        let key = DataKey::MintApproved(article_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(NFTError::MintNotApproved);
+        }
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Extend the contract instance (admin config, threshold) per the TTL policy
+    fn extend_instance(env: &Env) {
+        let ttl = Self::get_ttl_config(env.clone());
+        env.storage().instance().extend_ttl(ttl.threshold, ttl.extend_to);
+    }
+
+    /// Extend a persistent entry per the TTL policy, if it exists
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        if env.storage().persistent().has(key) {
+            let ttl = Self::get_ttl_config(env.clone());
+            env.storage().persistent().extend_ttl(key, ttl.threshold, ttl.extend_to);
+        }
+    }
+
+    /// Extend everything written by a mint
+    fn extend_minted(env: &Env, token_id: u64, article_id: &Symbol, owner: &Address) {
+        Self::extend_instance(env);
+        Self::extend_persistent(env, &DataKey::Token(token_id));
+        Self::extend_persistent(env, &DataKey::ArticleToken(article_id.clone()));
+        Self::extend_persistent(env, &DataKey::OwnerTokens(owner.clone()));
+        Self::extend_persistent(env, &DataKey::TokenCounter);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{storage::{Instance as _, Persistent as _}, Address as _, Ledger as _};
+    use soroban_sdk::{testutils::Events as _, Event as _, InvokeError, IntoVal};
+    use stellar_access::access_control::AccessControlError;
+    use stellar_access::ownable::OwnableError;
+    use stellar_contract_utils::pausable::PausableError;
+
+    // The current code as built by `stellar contract build`; refresh it after changing the contract
+    const NFT_WASM: &[u8] = include_bytes!("../testdata/quilltip_article_nft.wasm");
+
+    // The original release (schema 0), built from its source
+    const NFT_V0_WASM: &[u8] = include_bytes!("../testdata/quilltip_article_nft_v0.wasm");
+
+    /// Entrypoints of the original release the upgrade tests drive
+    #[allow(dead_code)] // Only the generated client is used
+    #[contractclient(name = "ArticleNFTV0Client")]
+    pub trait ArticleNFTV0 {
+        fn initialize(env: Env, admin: Address, tip_threshold: Option<i128>);
+        fn mint_article_nft(env: Env, author: Address, article_id: Symbol, tip_amount: i128, metadata_url: String) -> u64;
+        fn mint_article_nft_with_arweave(
+            env: Env,
+            author: Address,
+            article_id: Symbol,
+            tip_amount: i128,
+            metadata_url: String,
+            arweave_tx_id: String,
+        ) -> u64;
+        fn transfer(env: Env, from: Address, to: Address, token_id: u64);
+    }
+
+    /// Stand-in for the tipping contract's article registry
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_article_author(env: Env, article_id: Symbol, author: Address) {
+            env.storage().persistent().set(&article_id, &author);
+        }
+
+        pub fn get_article_author(env: Env, article_id: Symbol) -> Option<Address> {
+            env.storage().persistent().get(&article_id)
+        }
+
+        pub fn set_article_total_tips(env: Env, article_id: Symbol, total: i128) {
+            env.storage().persistent().set(&(article_id, env.current_contract_address()), &total);
+        }
+
+        pub fn get_native_token(env: Env) -> Address {
+            env.current_contract_address()
+        }
+
+        pub fn get_article_total_tips(env: Env, article_id: Symbol, token: Address) -> i128 {
+            env.storage().persistent().get(&(article_id, token)).unwrap_or(0)
+        }
+    }
+
+    fn create_registry<'a>(env: &Env) -> MockRegistryClient<'a> {
+        let registry_id = env.register(MockRegistry, ());
+        MockRegistryClient::new(env, &registry_id)
+    }
     
     #[test]
     fn test_mint_and_transfer() {
@@ -369,25 +811,36 @@ mod tests {
         env.mock_all_auths();
         
         // Initialize
-        client.initialize(&admin, &Some(50_000_000)); // 5 XLM threshold
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &Some(50_000_000)); // 5 XLM threshold
         
         // Mint NFT
         let article_id = Symbol::new(&env, "article_123");
+        registry.set_article_author(&article_id, &author);
         let metadata_url = String::from_str(&env, "https://quilltip.com/article/123");
-        let token_id = client.mint_article_nft(
-            &author,
-            &article_id,
-            &60_000_000, // 6 XLM (above threshold)
-            &metadata_url
-        );
+        registry.set_article_total_tips(&article_id, &60_000_000); // 6 XLM (above threshold)
+        let token_id = client.mint_article_nft(&author, &article_id, &metadata_url);
         
         assert_eq!(token_id, 1);
-        assert_eq!(client.get_owner(&token_id), author);
+        assert_eq!(client.owner_of(&token_id), author);
         assert!(client.is_article_minted(&article_id));
         
         // Transfer NFT
         client.transfer(&author, &buyer, &token_id);
-        assert_eq!(client.get_owner(&token_id), buyer);
+
+        // Transfer keeps its original (name, token_id) / (from, to, article) layout
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (Symbol::new(&env, "transfer"), token_id).into_val(&env),
+                    (author.clone(), buyer.clone(), article_id.clone()).into_val(&env)
+                )
+            ]
+        );
+        assert_eq!(client.owner_of(&token_id), buyer);
         
         // Check owned tokens
         let buyer_tokens = client.get_owned_tokens(&buyer);
@@ -396,7 +849,6 @@ mod tests {
     }
     
     #[test]
-    #[should_panic(expected = "Article already minted as NFT")]
     fn test_double_mint_prevention() {
         let env = Env::default();
         let contract_id = env.register(ArticleNFTContract, ());
@@ -406,20 +858,23 @@ mod tests {
         let author = Address::generate(&env);
         
         env.mock_all_auths();
-        client.initialize(&admin, &None);
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
         
         let article_id = Symbol::new(&env, "article_456");
+        registry.set_article_author(&article_id, &author);
+        registry.set_article_total_tips(&article_id, &200_000_000);
         let metadata_url = String::from_str(&env, "https://quilltip.com/article/456");
         
         // First mint succeeds
-        client.mint_article_nft(&author, &article_id, &200_000_000, &metadata_url);
+        client.mint_article_nft(&author, &article_id, &metadata_url);
         
-        // Second mint should panic
-        client.mint_article_nft(&author, &article_id, &300_000_000, &metadata_url);
+        // Second mint should fail
+        let result = client.try_mint_article_nft(&author, &article_id, &metadata_url);
+        assert_eq!(result, Err(Ok(NFTError::AlreadyMinted)));
     }
     
     #[test]
-    #[should_panic(expected = "Tip amount below threshold")]
     fn test_threshold_enforcement() {
         let env = Env::default();
         let contract_id = env.register(ArticleNFTContract, ());
@@ -429,12 +884,460 @@ mod tests {
         let author = Address::generate(&env);
         
         env.mock_all_auths();
-        client.initialize(&admin, &Some(100_000_000)); // 10 XLM threshold
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &Some(100_000_000)); // 10 XLM threshold
         
         let article_id = Symbol::new(&env, "article_789");
+        registry.set_article_author(&article_id, &author);
         let metadata_url = String::from_str(&env, "https://quilltip.com/article/789");
         
         // Try to mint with insufficient tips
-        client.mint_article_nft(&author, &article_id, &50_000_000, &metadata_url); // Only 5 XLM
+        registry.set_article_total_tips(&article_id, &50_000_000); // Only 5 XLM
+        let result = client.try_mint_article_nft(&author, &article_id, &metadata_url);
+        assert_eq!(result, Err(Ok(NFTError::BelowThreshold)));
+
+        // The total comes from the tipping contract, not the caller
+        registry.set_article_total_tips(&article_id, &100_000_000);
+        let token_id = client.mint_article_nft(&author, &article_id, &metadata_url);
+        assert_eq!(client.get_token_by_article(&article_id).unwrap().tip_amount, 100_000_000);
+        assert_eq!(client.owner_of(&token_id), author);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_transfer_and_admin_errors() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
+        assert_eq!(
+            client.try_initialize(&admin, &registry.address, &None),
+            Err(Ok(NFTError::AlreadyInitialized))
+        );
+
+        let article_id = Symbol::new(&env, "article_999");
+        registry.set_article_author(&article_id, &author);
+        registry.set_article_total_tips(&article_id, &200_000_000);
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/999");
+        let token_id = client.mint_article_nft(&author, &article_id, &metadata_url);
+
+        assert_eq!(client.try_transfer(&stranger, &admin, &token_id), Err(Ok(NFTError::NotOwner)));
+        assert_eq!(client.try_owner_of(&42), Err(Ok(NFTError::TokenNotFound)));
+
+        // Owner-only entrypoints need the owner's signature
+        client.set_mint_approval_required(&false);
+        assert_eq!(env.auths()[0].0, admin);
+        env.set_auths(&[]);
+        assert!(client.try_set_mint_approval_required(&true).is_err());
+    }
+
+    #[test]
+    fn test_ownership_transfer() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        assert_eq!(
+            client.try_set_mint_approval_required(&true),
+            Err(Err(InvokeError::Contract(OwnableError::OwnerNotSet as u32)))
+        );
+        client.initialize(&admin, &registry.address, &None);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+
+        // Two-step: nothing changes until the new owner accepts
+        let live_until = env.ledger().sequence() + DAY_IN_LEDGERS;
+        client.transfer_ownership(&new_owner, &live_until);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        client.accept_ownership();
+        assert_eq!(env.auths()[0].0, new_owner);
+        assert_eq!(client.get_owner(), Some(new_owner.clone()));
+
+        client.set_mint_approval_required(&false);
+        assert_eq!(env.auths()[0].0, new_owner);
+
+        // The role admin moves with ownership, and only with it
+        let pauser = Symbol::new(&env, "pauser");
+        assert_eq!(client.get_admin(), Some(new_owner.clone()));
+        let unauthorized = Err(Ok(soroban_sdk::Error::from_contract_error(NFTError::Unauthorized as u32)));
+        assert_eq!(client.try_transfer_admin_role(&admin, &live_until), unauthorized);
+        assert_eq!(client.try_accept_admin_transfer(), unauthorized);
+        assert_eq!(client.try_renounce_admin(), unauthorized);
+        assert!(client.try_grant_role(&admin, &admin, &pauser).is_err());
+        client.grant_role(&new_owner, &admin, &pauser);
+
+        // Renouncing locks owner-only entrypoints and role management for good
+        client.renounce_ownership();
+        assert_eq!(client.get_owner(), None);
+        assert_eq!(client.get_admin(), None);
+        assert!(client.try_revoke_role(&new_owner, &admin, &pauser).is_err());
+        assert_eq!(
+            client.try_set_ttl_config(&DAY_IN_LEDGERS, &(60 * DAY_IN_LEDGERS)),
+            Err(Err(InvokeError::Contract(OwnableError::OwnerNotSet as u32)))
+        );
+    }
+
+    #[test]
+    fn test_roles_and_mint_approval() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let curator = Address::generate(&env);
+        let pauser = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        assert_eq!(
+            client.try_initialize(&admin, &registry.address, &Some(0)),
+            Err(Ok(NFTError::InvalidTipThreshold))
+        );
+        client.initialize(&admin, &registry.address, &None);
+        assert_eq!(client.get_admin(), Some(admin.clone()));
+
+        // Roles are granted explicitly, even to the owner
+        let unauthorized = Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)));
+        assert_eq!(client.try_set_tip_threshold(&admin, &1), unauthorized);
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+        assert_eq!(client.try_set_tip_threshold(&admin, &0), Err(Ok(NFTError::InvalidTipThreshold)));
+        client.set_tip_threshold(&admin, &1);
+        let updated = TipThresholdUpdatedEvent { old_threshold: DEFAULT_TIP_THRESHOLD, new_threshold: 1 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), updated.topics(&env), updated.data(&env))]
+        );
+        assert_eq!(client.get_tip_threshold(), 1);
+
+        client.grant_role(&admin, &pauser, &Symbol::new(&env, "pauser"));
+        client.pause(&pauser);
+        assert!(client.is_paused());
+        assert_eq!(client.try_unpause(&admin), unauthorized);
+        client.unpause(&pauser);
+
+        // With approval on, each mint needs a mint_approver's sign-off
+        client.set_mint_approval_required(&true);
+        let article_id = Symbol::new(&env, "article_1");
+        registry.set_article_author(&article_id, &author);
+        registry.set_article_total_tips(&article_id, &200_000_000);
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/1");
+        assert_eq!(
+            client.try_mint_article_nft(&author, &article_id, &metadata_url),
+            Err(Ok(NFTError::MintNotApproved))
+        );
+        assert_eq!(client.try_approve_mint(&curator, &article_id), unauthorized);
+
+        client.grant_role(&admin, &curator, &Symbol::new(&env, "mint_approver"));
+        client.approve_mint(&curator, &article_id);
+        assert_eq!(env.auths()[0].0, curator);
+        let approved = MintApprovedEvent { article_id: article_id.clone(), approver: curator.clone() };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), approved.topics(&env), approved.data(&env))]
+        );
+        assert!(client.is_mint_approved(&article_id));
+        client.mint_article_nft(&author, &article_id, &metadata_url);
+        assert!(!client.is_mint_approved(&article_id));
+        assert_eq!(
+            client.try_initialize(&admin, &registry.address, &None),
+            Err(Ok(NFTError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_upgrade_and_migrate() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        let article_1 = Symbol::new(&env, "article_1");
+        let article_2 = Symbol::new(&env, "article_2");
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/1");
+        let arweave_tx_id = String::from_str(&env, "arweave_tx");
+
+        // NFTs minted and traded by the original release through its own entrypoints
+        let contract_id = env.register(NFT_V0_WASM, ());
+        let legacy = ArticleNFTV0Client::new(&env, &contract_id);
+        legacy.initialize(&admin, &None);
+        assert_eq!(legacy.mint_article_nft(&author, &article_1, &150_000_000, &metadata_url), 1);
+        assert_eq!(
+            legacy.mint_article_nft_with_arweave(&author, &article_2, &100_000_000, &metadata_url, &arweave_tx_id),
+            2
+        );
+        legacy.transfer(&author, &buyer, &1);
+
+        // The original release has no upgrade entrypoint, so its code is
+        // swapped from inside the contract
+        let wasm_hash = env.deployer().upload_contract_wasm(NFT_WASM);
+        env.as_contract(&contract_id, || env.deployer().update_current_contract_wasm(wasm_hash.clone()));
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+        assert_eq!(client.get_schema_version(), 0);
+        assert_eq!(client.get_owner(), None);
+        let registry = create_registry(&env);
+        assert_eq!(
+            client.try_initialize(&author, &registry.address, &None),
+            Err(Ok(NFTError::AlreadyInitialized))
+        );
+
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        let migrated = MigratedEvent { from_version: 0, to_version: SCHEMA_VERSION };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), migrated.topics(&env), migrated.data(&env))]
+        );
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(env.events().all(), vec![&env]);
+        assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        assert_eq!(client.get_admin(), Some(admin.clone()));
+        assert_eq!(client.get_tip_threshold(), DEFAULT_TIP_THRESHOLD);
+
+        // Minted NFTs and their ownership carry over
+        let nft = client.get_token_by_article(&article_1).unwrap();
+        assert_eq!((nft.token_id, nft.owner, nft.minter, nft.tip_amount), (1, buyer.clone(), author.clone(), 150_000_000));
+        let nft = client.get_token_by_article(&article_2).unwrap();
+        assert_eq!(nft.arweave_tx_id, Some(arweave_tx_id));
+        assert_eq!(client.owner_of(&2), author);
+        assert_eq!(client.get_owned_tokens(&author), vec![&env, 2]);
+        assert_eq!(client.get_owned_tokens(&buyer), vec![&env, 1]);
+        assert!(client.is_article_minted(&article_1));
+
+        // The owner connects the registry, after which minting works again
+        let article_3 = Symbol::new(&env, "article_3");
+        assert_eq!(
+            client.try_mint_article_nft(&author, &article_3, &metadata_url),
+            Err(Ok(NFTError::NotInitialized))
+        );
+        client.set_tipping_contract(&registry.address);
+        assert_eq!(env.auths()[0].0, admin);
+        registry.set_article_author(&article_3, &author);
+        registry.set_article_total_tips(&article_3, &DEFAULT_TIP_THRESHOLD);
+        assert_eq!(client.mint_article_nft(&author, &article_3, &metadata_url), 3);
+
+        // Upgrades need code that has been uploaded
+        assert!(client.try_upgrade(&BytesN::from_array(&env, &[0; 32])).is_err());
+
+        // Later upgrades go through the owner-gated entrypoint
+        env.set_auths(&[]);
+        assert!(client.try_upgrade(&wasm_hash).is_err());
+        env.mock_all_auths();
+        client.upgrade(&wasm_hash);
+        assert_eq!(env.auths()[0].0, admin);
+        let upgraded = UpgradedEvent { wasm_hash };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), upgraded.topics(&env), upgraded.data(&env))]
+        );
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(env.events().all(), vec![&env]);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        assert_eq!(client.owner_of(&1), buyer);
+        assert_eq!(client.get_token_by_article(&article_3).unwrap().owner, author);
+
+        // Fresh deployments start at the current schema
+        let fresh_id = env.register(ArticleNFTContract, ());
+        let fresh = ArticleNFTContractClient::new(&env, &fresh_id);
+        assert_eq!(fresh.try_migrate(), Err(Ok(NFTError::NotInitialized)));
+        fresh.initialize(&admin, &registry.address, &None);
+        assert_eq!(fresh.get_schema_version(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_pause_guards_mutating_entrypoints() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "pauser"));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "mint_approver"));
+        let article_1 = Symbol::new(&env, "article_1");
+        let article_2 = Symbol::new(&env, "article_2");
+        registry.set_article_author(&article_1, &author);
+        registry.set_article_total_tips(&article_1, &200_000_000);
+        registry.set_article_author(&article_2, &author);
+        registry.set_article_total_tips(&article_2, &200_000_000);
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/1");
+        let token_id = client.mint_article_nft(&author, &article_1, &metadata_url);
+        client.pause(&admin);
+
+        // Minting and transfers are blocked
+        let enforced = InvokeError::Contract(PausableError::EnforcedPause as u32);
+        assert_eq!(
+            client.try_mint_article_nft(&author, &article_2, &metadata_url),
+            Err(Err(enforced))
+        );
+        assert_eq!(
+            client.try_mint_article_nft_with_arweave(
+                &author,
+                &article_2,
+                &metadata_url,
+                &String::from_str(&env, "tx"),
+            ),
+            Err(Err(enforced))
+        );
+        assert_eq!(client.try_transfer(&author, &buyer, &token_id), Err(Err(enforced)));
+
+        // Recovery stays open
+        client.set_tip_threshold(&admin, &1);
+        client.set_mint_approval_required(&true);
+        client.approve_mint(&admin, &article_2);
+        client.set_tipping_contract(&registry.address);
+        client.bump_token(&token_id);
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+
+        client.unpause(&admin);
+        client.transfer(&author, &buyer, &token_id);
+        assert_eq!(client.owner_of(&token_id), buyer);
+        assert_eq!(client.mint_article_nft(&author, &article_2, &metadata_url), 2);
+    }
+
+    #[test]
+    fn test_only_registered_author_can_mint() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let impostor = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
+        assert_eq!(client.get_tipping_contract(), registry.address);
+
+        let article_id = Symbol::new(&env, "article_321");
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/321");
+
+        // Unregistered article
+        let result = client.try_mint_article_nft(&author, &article_id, &metadata_url);
+        assert_eq!(result, Err(Ok(NFTError::ArticleNotRegistered)));
+
+        registry.set_article_author(&article_id, &author);
+        registry.set_article_total_tips(&article_id, &200_000_000);
+
+        // Someone other than the registered author
+        let result = client.try_mint_article_nft(&impostor, &article_id, &metadata_url);
+        assert_eq!(result, Err(Ok(NFTError::NotArticleAuthor)));
+        let result = client.try_mint_article_nft_with_arweave(
+            &impostor,
+            &article_id,
+            &metadata_url,
+            &String::from_str(&env, "arweave_tx"),
+        );
+        assert_eq!(result, Err(Ok(NFTError::NotArticleAuthor)));
+
+        let token_id = client.mint_article_nft(&author, &article_id, &metadata_url);
+        assert_eq!(client.owner_of(&token_id), author);
+    }
+
+    #[test]
+    fn test_ttl_extended_and_bumped() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let author = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
+
+        let article_id = Symbol::new(&env, "article_ttl");
+        registry.set_article_author(&article_id, &author);
+        registry.set_article_total_tips(&article_id, &200_000_000);
+        let metadata_url = String::from_str(&env, "https://quilltip.com/article/ttl");
+        let token_id = client.mint_article_nft(&author, &article_id, &metadata_url);
+
+        let token_key = DataKey::Token(token_id);
+        let article_key = DataKey::ArticleToken(article_id.clone());
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&token_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&article_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().instance().get_ttl(), DEFAULT_TTL_EXTEND_TO);
+        });
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 25 * DAY_IN_LEDGERS);
+        client.transfer(&author, &buyer, &token_id);
+        let buyer_key = DataKey::OwnerTokens(buyer.clone());
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&token_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&buyer_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&article_key), 5 * DAY_IN_LEDGERS);
+        });
+
+        // The keeper bump covers the article lookup and the current owner
+        client.bump_token(&token_id);
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&article_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&buyer_key), DEFAULT_TTL_EXTEND_TO);
+        });
+
+        // So do reads
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 25 * DAY_IN_LEDGERS);
+        assert_eq!(client.get_token_by_article(&article_id).unwrap().owner, buyer);
+        assert_eq!(client.get_owned_tokens(&buyer), vec![&env, token_id]);
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&token_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&article_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&buyer_key), DEFAULT_TTL_EXTEND_TO);
+        });
+        assert_eq!(client.try_bump_token(&42), Err(Ok(NFTError::TokenNotFound)));
+    }
+
+    #[test]
+    fn test_ttl_config() {
+        let env = Env::default();
+        let contract_id = env.register(ArticleNFTContract, ());
+        let client = ArticleNFTContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+        let registry = create_registry(&env);
+        client.initialize(&admin, &registry.address, &None);
+        assert_eq!(
+            client.get_ttl_config(),
+            TtlConfig { threshold: DEFAULT_TTL_THRESHOLD, extend_to: DEFAULT_TTL_EXTEND_TO }
+        );
+
+        assert_eq!(
+            client.try_set_ttl_config(&DAY_IN_LEDGERS, &DAY_IN_LEDGERS),
+            Err(Ok(NFTError::InvalidTtlConfig))
+        );
+        assert_eq!(
+            client.try_set_ttl_config(&DAY_IN_LEDGERS, &u32::MAX),
+            Err(Ok(NFTError::InvalidTtlConfig))
+        );
+
+        client.set_ttl_config(&DAY_IN_LEDGERS, &(60 * DAY_IN_LEDGERS));
+        assert_eq!(
+            client.get_ttl_config(),
+            TtlConfig { threshold: DAY_IN_LEDGERS, extend_to: 60 * DAY_IN_LEDGERS }
+        );
+    }
+}
@@ -1,561 +1,6101 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, token, vec, Address, Env, String, Symbol, Vec};
-use stellar_contract_utils::pausable::{self, Pausable, PausableError};
-use stellar_access::ownable::{self, Ownable, OwnableError};
-use stellar_macros::{only_owner, when_not_paused};
+use soroban_sdk::{contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, token, vec, Address, BytesN, Env, Map, String, Symbol, Vec};
+use stellar_contract_utils::pausable;
+use stellar_access::access_control::{self, AccessControl};
+use stellar_access::ownable::{self, Ownable};
+use stellar_macros::{default_impl, only_owner, only_role, when_not_paused};
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct SimpleTip {
     pub tipper: Address,
+    pub token: Address,
     pub amount: i128,
     pub timestamp: u64,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct TipReceipt {
     pub tip_id: u64,
+    pub token: Address,
     pub amount_sent: i128,
     pub author_received: i128,
     pub platform_fee: i128,
     pub timestamp: u64,
+    pub payouts: Vec<RecipientPayout>,  // What each co-author received; sums to author_received
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RecipientPayout {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// A co-author's share of an article's tips
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitShare {
+    pub recipient: Address,
+    pub share_bps: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct HighlightTip {
     pub highlight_id: String,    // Unique highlight identifier (SHA256)
     pub article_id: Symbol,       // Parent article (Convex ID - alphanumeric, Symbol-safe)
     pub tipper: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Lifetime earnings of an author across all their articles
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuthorStats {
+    pub tip_count: u32,
+    pub first_tip_at: u64,
+    pub last_tip_at: u64,
+    pub tokens: Vec<AuthorTokenStats>,  // One entry per token the author was tipped in
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuthorTokenStats {
+    pub token: Address,
+    pub tip_count: u32,
+    pub gross: i128,     // Sent by tippers
+    pub net: i128,       // Received by the author
+    pub fees: i128,      // Taken by the platform
+}
+
+/// Note attached to a tip: public text, or the hash of a message kept off-chain
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TipMessage {
+    Text(String),          // At most MAX_MESSAGE_LEN bytes
+    Hash(BytesN<32>),
+}
+
+/// One tip in a `tip_batch` call; set `highlight_id` to tip a highlight of the article
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipItem {
+    pub article_id: Symbol,
+    pub highlight_id: Option<String>,
+    pub token: Address,
+    pub amount: i128,
+    pub arweave_tx_id: Option<String>,
+    pub message: Option<String>,          // Public text; see TipMessage::Text
+    pub message_hash: Option<BytesN<32>>, // Off-chain message hash; exclusive with `message`
+}
+
+/// A tip looked up by its id, wherever it was sent
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipRecord {
+    pub tip_id: u64,
+    pub article_id: Option<Symbol>,     // None for subscription charges
+    pub highlight_id: Option<String>,   // Set for highlight tips
+    pub subscription_id: Option<u64>,   // Set for subscription charges
+    pub tipper: Address,
+    pub author: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenTotal {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Lifetime giving of a tipper
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipperStats {
+    pub tip_count: u32,
+    pub authors_supported: u32,     // Distinct authors tipped
+    pub given: Vec<TokenTotal>,     // Total sent per token, fees included
+}
+
+/// What one tipper has given one author
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Supporter {
+    pub tipper: Address,
+    pub given: Vec<TokenTotal>,
+}
+
+/// A tipper's place among an author's top supporters in one token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RankedSupporter {
+    pub tipper: Address,
+    pub amount: i128,               // Total given to the author in the token, fees included
+}
+
+/// Stable error codes returned by every fallible entrypoint.
+/// Pause checks surface OZ `PausableError::EnforcedPause` (1000) instead.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TippingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    BelowMinimumTip = 4,
+    FeeTooHigh = 5,
+    TokenNotAllowed = 6,
+    InvalidMinimumTip = 7,
+    ArticleNotRegistered = 8,
+    ArticleAlreadyRegistered = 9,
+    InvalidTtlConfig = 10,
+    EmptyBatch = 11,
+    BatchTooLarge = 12,
+    InvalidSplits = 13,
+    SubscriptionNotFound = 14,
+    SubscriptionInactive = 15,
+    SubscriptionNotDue = 16,
+    InvalidSubscription = 17,
+    EscrowNotFound = 18,
+    EscrowNotExpired = 19,
+    PendingTipNotFound = 20,
+    PendingWindowOpen = 21,
+    PendingWindowClosed = 22,
+    InvalidPendingWindow = 23,
+    InvalidMessage = 24,
+    MessagesDisabled = 25,
+    InvalidFeeTiers = 26,
+    InvalidFeeHoliday = 27,
+    FeeChangeNotFound = 28,
+    FeeChangeNotReady = 29,
+    InvalidFeeChange = 30,
+    InvalidFeeChangePolicy = 31,
+    InvalidFeeWithdrawal = 32,
+    InsufficientFees = 33,
+    TipLimitExceeded = 34,
+    InvalidTipLimits = 35,
+}
+
+/// A tip in custody during the refund window, released by `finalize_tip`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingTip {
+    pub tip_id: u64,
+    pub tipper: Address,
+    pub article_id: Symbol,
+    pub highlight_id: Option<String>,
+    pub token: Address,
     pub amount: i128,
+    pub release_ledger: u32,     // First ledger the tip can be finalized on
+}
+
+/// A tip held by the contract until the article's author claims it
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EscrowedTip {
+    pub index: u32,              // Position in the article's escrow, used to refund
+    pub tip_id: u64,
+    pub tipper: Address,
+    pub highlight_id: Option<String>,
+    pub token: Address,
+    pub amount: i128,            // Full amount; the fee is taken on claim
     pub timestamp: u64,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SubscriptionStatus {
+    Active,
+    Paused,
+    Cancelled,
+    Completed,   // All `max_periods` charged
+}
+
+/// A recurring tip from a reader to an author, pulled by a keeper through a
+/// token allowance the subscriber grants this contract
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Subscription {
+    pub id: u64,
+    pub subscriber: Address,
+    pub author: Address,
+    pub token: Address,
+    pub amount: i128,            // Charged per period, before the platform fee
+    pub period_secs: u64,
+    pub max_periods: u32,
+    pub periods_charged: u32,
+    pub next_charge_at: u64,     // Ledger timestamp the next period becomes due
+    pub status: SubscriptionStatus,
+}
+
+/// Ledger TTL policy: entries whose TTL drops below `threshold` are
+/// extended to live for `extend_to` more ledgers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TtlConfig {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+/// Fee change waiting out the timelock
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeChange {
+    pub new_fee_bps: u32,
+    pub effective_ledger: u32,
+}
+
+/// Limits on fee changes: minimum notice in ledgers and largest move per change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeChangePolicy {
+    pub delay_ledgers: u32,
+    pub max_step_bps: u32,
+}
+
+/// Per-tipper anti-spam limits for one token; a zero field is not enforced
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipLimits {
+    pub window_ledgers: u32,       // Length of the per-article window
+    pub max_tips_per_window: u32,  // Tips one tipper may send one article per window
+    pub max_tip: i128,             // Largest single tip
+    pub daily_cap: i128,           // Most one tipper may send over any DAY_IN_LEDGERS
+}
+
+/// A tipper's tips to one article in the current window
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipWindowUsage {
+    pub window: u32,               // Ledger sequence / window_ledgers
+    pub count: u32,
+}
+
+/// Amount a tipper sent during one SPEND_BUCKET_LEDGERS bucket
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TipSpend {
+    pub bucket_start: u32,
+    pub amount: i128,
+}
+
+/// Volume tier: authors tipped at least `min_volume` of the native token over
+/// their lifetime (gross, fees included) pay `fee_bps`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeTier {
+    pub min_volume: i128,
+    pub fee_bps: u32,
+}
+
+/// Promotional fee cap for ledgers `start_ledger..=end_ledger`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeHoliday {
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub fee_bps: u32,
+}
+
+/// Fee/net split a tip to an author would get right now
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeQuote {
+    pub fee_bps: u32,
+    pub fee: i128,
+    pub net: i128,
+}
+
+/// Partial sums of an article's direct tips while recompute_article_totals pages through them
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TotalRecompute {
+    pub next_index: u32,
+    pub sums: Map<Address, i128>,
+}
+
+// ========== EVENTS ==========
+// Every event is topic-prefixed with its name and a schema version.
+// Bump the version topic whenever an event's fields change.
+
+/// A tip on an article or one of its highlights
+/// v2: adds `payouts`; `recipient` is the registered author
+/// v3: adds `message`
+#[contractevent(topics = ["tip", "v3"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub tip_id: u64,
+    pub highlight_id: Option<String>,
+    pub token: Address,
+    pub tipper: Address,
+    pub recipient: Address,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+    pub payouts: Vec<RecipientPayout>,
+    pub message: Option<TipMessage>,
+}
+
+/// The Arweave copy of a tipped article or highlight, emitted after its TipEvent
+/// v2: one event for articles and highlights (highlights used
+/// `highlight_tip_arweave`); adds `tip_id`, `highlight_id` and `token`
+#[contractevent(topics = ["tip_with_arweave", "v2"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArweaveTipEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub tip_id: u64,
+    pub highlight_id: Option<String>,
+    pub tipper: Address,
+    pub author: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub arweave_tx_id: String,
+}
+
+#[contractevent(topics = ["public_messages_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicMessagesUpdatedEvent {
+    #[topic]
+    pub author: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["splits_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitsUpdatedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub splits: Vec<SplitShare>,
+}
+
+#[contractevent(topics = ["initialized", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub platform_address: Address,
+    pub native_token: Address,
+    pub fee_bps: u32,
+}
+
+#[contractevent(topics = ["platform_address_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformAddressUpdatedEvent {
+    pub old_address: Address,
+    pub new_address: Address,
+}
+
+#[contractevent(topics = ["fees_withdrawn", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesWithdrawnEvent {
+    #[topic]
+    pub token: Address,
+    pub caller: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["fee_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeUpdatedEvent {
+    pub old_fee_bps: u32,
+    pub new_fee_bps: u32,
+}
+
+#[contractevent(topics = ["upgraded", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["migrated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[contractevent(topics = ["article_total_corrected", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArticleTotalCorrectedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub token: Address,
+    pub old_total: i128,
+    pub new_total: i128,
+}
+
+#[contractevent(topics = ["fee_change_proposed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeChangeProposedEvent {
+    pub old_fee_bps: u32,
+    pub new_fee_bps: u32,
+    pub effective_ledger: u32,
+}
+
+#[contractevent(topics = ["fee_change_cancelled", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeChangeCancelledEvent {
+    pub new_fee_bps: u32,
+    pub effective_ledger: u32,
+}
+
+#[contractevent(topics = ["fee_change_policy_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeChangePolicyUpdatedEvent {
+    pub delay_ledgers: u32,
+    pub max_step_bps: u32,
+}
+
+#[contractevent(topics = ["fee_tiers_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTiersUpdatedEvent {
+    pub tiers: Vec<FeeTier>,
+}
+
+/// `fee_bps` is None when the override is removed
+#[contractevent(topics = ["author_fee_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorFeeUpdatedEvent {
+    #[topic]
+    pub author: Address,
+    pub fee_bps: Option<u32>,
+}
+
+/// `holiday` is None when the promotion is cleared
+#[contractevent(topics = ["fee_holiday_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeHolidayUpdatedEvent {
+    pub holiday: Option<FeeHoliday>,
+}
+
+#[contractevent(topics = ["tip_limits_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipLimitsUpdatedEvent {
+    #[topic]
+    pub token: Address,
+    pub limits: Option<TipLimits>,
+}
+
+#[contractevent(topics = ["token_allowed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenAllowedEvent {
+    #[topic]
+    pub token: Address,
+    pub min_amount: i128,
+}
+
+#[contractevent(topics = ["token_removed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenRemovedEvent {
+    #[topic]
+    pub token: Address,
+}
+
+#[contractevent(topics = ["article_registered", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArticleRegisteredEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub author: Address,
+}
+
+#[contractevent(topics = ["article_reassigned", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArticleReassignedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub old_author: Address,
+    pub new_author: Address,
+}
+
+#[contractevent(topics = ["cosign_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationCosignEvent {
+    pub required: bool,
+}
+
+#[contractevent(topics = ["ttl_config_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfigUpdatedEvent {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+/// v2: `admin` becomes `caller`, the pauser who made the change
+#[contractevent(topics = ["pause_changed", "v2"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseChangedEvent {
+    pub caller: Address,
+    pub paused: bool,
+}
+
+#[contractevent(topics = ["subscription_created", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionCreatedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub subscriber: Address,
+    pub author: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub period_secs: u64,
+    pub max_periods: u32,
+}
+
+#[contractevent(topics = ["subscription_charged", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionChargedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub period: u32,            // 1-based period number
+    pub token: Address,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+}
+
+/// `reason` is `insufficient_allowance` or `insufficient_balance`
+#[contractevent(topics = ["subscription_charge_failed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionChargeFailedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub period: u32,
+    pub reason: Symbol,
+}
+
+#[contractevent(topics = ["subscription_status", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionStatusEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub status: SubscriptionStatus,
+}
+
+/// A tip to an article without a registered author, held in escrow
+#[contractevent(topics = ["tip_escrowed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipEscrowedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub tip_id: u64,
+    pub index: u32,
+    pub highlight_id: Option<String>,
+    pub token: Address,
+    pub tipper: Address,
+    pub amount: i128,
+}
+
+/// Each settled tip also emits a `TipEvent`
+#[contractevent(topics = ["escrow_claimed", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowClaimedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub author: Address,
+    pub settled: u32,
+}
+
+#[contractevent(topics = ["escrow_refunded", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefundedEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub tip_id: u64,
+    pub tipper: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// A tip held for the refund window; finalizing it emits a `TipEvent`
+#[contractevent(topics = ["tip_pending", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TipPendingEvent {
+    #[topic]
+    pub article_id: Symbol,
+    pub tip_id: u64,
+    pub highlight_id: Option<String>,
+    pub token: Address,
+    pub tipper: Address,
+    pub amount: i128,
+    pub release_ledger: u32,
+}
+
+/// A pending tip returned to its tipper; `reversed` when the owner did it on dispute
+#[contractevent(topics = ["pending_tip_refunded", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTipRefundedEvent {
+    #[topic]
+    pub tip_id: u64,
+    pub tipper: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub reversed: bool,
+}
+
+#[contractevent(topics = ["pending_window_updated", "v1"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingWindowUpdatedEvent {
+    pub ledgers: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Admin,
     PlatformAddress,
     PlatformFeeBps,
-    ArticleTips(Symbol),
-    ArticleTotalTips(Symbol),  // Track total tips per article for NFT threshold
+    ArticleTipCount(Symbol),            // Article → number of tips stored
+    ArticleTip(Symbol, u32),            // (article, index) → SimpleTip
+    ArticleTotalTips(Symbol, Address),  // (article, token) → total tips incl. highlights, for NFT threshold
+    ArticleHighlightTotal(Symbol, Address), // (article, token) → portion of the total tipped via highlights
     TipCounter,
-    TotalVolume,
-    HighlightTips(String),     // Highlight ID → Tips
-    Paused,                    // Emergency pause state (OZ Pausable)
+    TotalVolume(Address),      // Token → total tipped volume
+    HighlightTipCount(String),        // Highlight ID → number of tips stored
+    HighlightTip(String, u32),        // (highlight, index) → HighlightTip
+    AllowedTokens,             // Tokens accepted for tipping (owner managed)
+    TokenMinimum(Address),     // Token → minimum tip amount in its smallest unit
+    NativeToken,               // Network's native asset contract (supplied on initialize)
+    ArticleAuthor(Symbol),     // Article → registered author payout address
+    RegistrationCosign,        // Whether article registration needs platform co-signature
+    TtlConfig,                 // TTL bump policy for instance and persistent entries
+    AuthorStats(Address),      // Author → AuthorStats
+    ArticleSplits(Symbol),     // Article → Vec<SplitShare>, absent when the author takes everything
+    Tip(u64),                  // Tip ID → TipRecord
+    TipperTip(Address, u32),   // (tipper, index) → tip ID
+    TipperStats(Address),      // Tipper → TipperStats
+    SupporterGiven(Address, Address),  // (author, tipper) → Vec<TokenTotal>
+    SupporterCount(Address),   // Author → number of distinct supporters
+    Supporter(Address, u32),   // (author, index) → tipper address
+    TopSupporters(Address, Address),  // (author, token) → Vec<RankedSupporter>, largest first
+    SubscriptionCounter,       // Last subscription ID issued
+    Subscription(u64),         // Subscription ID → Subscription
+    EscrowCount(Symbol),       // Article → number of tips ever escrowed
+    EscrowHead(Symbol),        // Article → first escrow index not yet claimed
+    EscrowTip(Symbol, u32),    // (article, index) → EscrowedTip, removed once claimed or refunded
+    EscrowTotal(Symbol, Address),  // (article, token) → amount currently held
+    PendingWindow,             // Ledgers tips stay refundable before release; 0 pays out immediately
+    PendingTip(u64),           // Tip ID → PendingTip
+    TipMessage(u64),           // Tip ID → TipMessage, for tips sent with one
+    PublicMessagesOff(Address),  // Author → true when they refuse text messages
+    FeeTiers,                  // Vec<FeeTier> by ascending min_volume; empty uses PlatformFeeBps
+    AuthorFee(Address),        // Author → negotiated fee bps, ahead of tiers
+    FeeHoliday,                // Active or scheduled FeeHoliday
+    PendingFeeChange,          // Proposed FeeChange, until activated or cancelled
+    FeeChangePolicy,           // FeeChangePolicy; defaults apply when unset
+    SchemaVersion,             // Storage layout version, see SCHEMA_VERSION
+    AccruedFees(Address),      // Token → platform fees held by the contract
+    WithdrawnFees(Address),    // Token → platform fees withdrawn over the contract's lifetime
+    TipLimits(Address),        // Token → TipLimits; absent means unlimited
+    TipWindow(Address, Symbol, Address),  // Temporary: (tipper, article, token) → TipWindowUsage
+    TipSpend(Address, Address),  // Temporary: (tipper, token) → Vec<TipSpend> for the last day
+    LegacyTipCursor(Symbol),   // Article → next legacy tip to move, while its migration is in progress
+    LegacyHighlightTipCursor(String),  // Highlight ID → next legacy tip to move, while in progress
+    TotalRecompute(Symbol),    // Article → TotalRecompute, while its totals are being rebuilt
+    ArticleTokens(Symbol),     // Article → tokens it holds a total in, allowed or not
+}
+
+/// Storage layout written by the original deployment (schema 0), before tips
+/// carried a token and were split into per-index entries. Only read by the
+/// migrations.
+#[derive(Clone)]
+#[contracttype]
+pub enum LegacyDataKey {
+    Admin,                     // Admin address, now the OZ owner and role admin
+    TotalVolume,               // Volume in the native token
+    ArticleTotalTips(Symbol),  // Article → total in the native token
+    ArticleTips(Symbol),       // Article → Vec<LegacySimpleTip>
+    HighlightTips(String),     // Highlight ID → Vec<LegacyHighlightTip>
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LegacySimpleTip {
+    pub tipper: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LegacyHighlightTip {
+    pub highlight_id: String,
+    pub article_id: Symbol,
+    pub tipper: Address,
+    pub amount: i128,
+    pub timestamp: u64,
 }
 
 const MINIMUM_TIP_STROOPS: i128 = 100_000; // 0.01 XLM (approximately 1 cent)
 const DEFAULT_PLATFORM_FEE_BPS: u32 = 250; // 2.5%
+const MAX_FEE_BPS: u32 = 1000; // 10%
+const MAX_FEE_TIERS: u32 = 10;
+const DEFAULT_FEE_CHANGE_DELAY: u32 = 7 * DAY_IN_LEDGERS;
+const DEFAULT_MAX_FEE_STEP_BPS: u32 = 100; // 1 percentage point per change
+const MAX_PAGE_SIZE: u32 = 50; // Max tips returned per history query
+const MAX_MIGRATION_PAGE: u32 = 40; // Max legacy tips moved per call, under the ledger write limit
+const MAX_BATCH_SIZE: u32 = 10; // Max items per tip_batch call
+const MAX_SPLIT_RECIPIENTS: u32 = 10; // Max co-authors per article
+const ESCROW_EXPIRY_SECS: u64 = 180 * 24 * 60 * 60; // Unclaimed escrow is refundable after ~6 months
+const MAX_PENDING_WINDOW: u32 = 7 * DAY_IN_LEDGERS;
+const MIN_SUBSCRIPTION_PERIOD_SECS: u64 = 24 * 60 * 60; // Subscriptions charge at most daily
+const MAX_SUBSCRIPTION_PERIOD_SECS: u64 = 366 * 24 * 60 * 60; // and at least yearly
+const MAX_COLLECT_PERIODS: u32 = 6; // Max subscription periods charged per collect call, within the CPU budget
+const MAX_MESSAGE_LEN: u32 = 280; // Bytes of UTF-8 text per tip message
+const MAX_TOP_SUPPORTERS: u32 = 10; // Supporters ranked per author and token
+
+const DAY_IN_LEDGERS: u32 = 17_280; // ~5s ledgers
+const SPEND_BUCKET_LEDGERS: u32 = DAY_IN_LEDGERS / 24; // Daily caps roll forward hourly
 
-// Native XLM token contract on testnet
-const XLM_TOKEN_ADDRESS: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+const SCHEMA_VERSION: u32 = 1; // Bump with a migrate step whenever a DataKey layout changes
+const LEGACY_NATIVE_TOKEN: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"; // Hard-coded by schema 0
+const DEFAULT_TTL_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
+const DEFAULT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
 
 #[contract]
 pub struct TippingContract;
 
 #[contractimpl]
 impl TippingContract {
-    /// Initialize the contract with platform settings
-    pub fn initialize(env: Env, admin: Address, platform_address: Address, fee_bps: Option<u32>) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Contract already initialized");
+    /// Initialize the contract with platform settings; `admin` becomes the contract owner
+    /// `native_token` is the native asset contract of the target network (e.g. the XLM SAC)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        platform_address: Address,
+        native_token: Address,
+        fee_bps: Option<u32>,
+    ) -> Result<(), TippingError> {
+        // Not the owner: ownership can be renounced
+        if env.storage().instance().has(&DataKey::PlatformAddress) {
+            return Err(TippingError::AlreadyInitialized);
         }
         
         admin.require_auth();
         
         let platform_fee = fee_bps.unwrap_or(DEFAULT_PLATFORM_FEE_BPS);
+        if platform_fee > MAX_FEE_BPS {
+            return Err(TippingError::FeeTooHigh);
+        }
         
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        ownable::set_owner(&env, &admin);
+        access_control::set_admin(&env, &admin);
         env.storage().instance().set(&DataKey::PlatformAddress, &platform_address);
         env.storage().instance().set(&DataKey::PlatformFeeBps, &platform_fee);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
         env.storage().persistent().set(&DataKey::TipCounter, &0u64);
-        env.storage().persistent().set(&DataKey::TotalVolume, &0i128);
+
+        // The native token is always accepted out of the box
+        env.storage().instance().set(&DataKey::NativeToken, &native_token);
+        env.storage().instance().set(&DataKey::AllowedTokens, &vec![&env, native_token.clone()]);
+        env.storage().instance().set(&DataKey::TokenMinimum(native_token.clone()), &MINIMUM_TIP_STROOPS);
+
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::TipCounter);
+
+        InitializedEvent {
+            admin,
+            platform_address,
+            native_token,
+            fee_bps: platform_fee,
+        }
+        .publish(&env);
+
+        Ok(())
     }
     
-    /// Send a tip for an article
+    /// Send a tip for an article in any allowlisted token
+    /// The tip is paid to the article's registered author, or held in escrow until one claims it
+    #[when_not_paused]
     pub fn tip_article(
         env: Env,
         tipper: Address,
         article_id: Symbol,
-        author: Address,
+        token: Address,
         amount: i128,
-    ) -> TipReceipt {
+    ) -> Result<TipReceipt, TippingError> {
         tipper.require_auth();
-        
-        // Validate minimum amount
-        if amount < MINIMUM_TIP_STROOPS {
-            panic!("Amount below minimum tip");
-        }
-        
-        // Get platform settings
-        let platform_address: Address = env.storage()
-            .instance()
-            .get(&DataKey::PlatformAddress)
-            .expect("Platform address not set");
-        
-        let platform_fee_bps: u32 = env.storage()
-            .instance()
-            .get(&DataKey::PlatformFeeBps)
-            .unwrap_or(DEFAULT_PLATFORM_FEE_BPS);
-        
-        // Calculate fees
-        let platform_fee = (amount * platform_fee_bps as i128) / 10_000;
-        let author_share = amount - platform_fee;
-        
-        // Get XLM token client
-        let xlm_address = Address::from_string(&String::from_str(&env, XLM_TOKEN_ADDRESS));
-        let xlm_client = token::TokenClient::new(&env, &xlm_address);
-        
-        // Transfer author's share
-        xlm_client.transfer(&tipper, &author, &author_share);
-        
-        // Transfer platform fee
-        if platform_fee > 0 {
-            xlm_client.transfer(&tipper, &platform_address, &platform_fee);
+        Self::execute_tip(&env, &tipper, article_id, None, token, amount, None)
+    }
+    
+    /// Get a page of an article's tips, oldest first
+    /// `limit` is capped at MAX_PAGE_SIZE
+    pub fn get_article_tips(env: Env, article_id: Symbol, start: u32, limit: u32) -> Vec<SimpleTip> {
+        let count = Self::get_article_tip_count(env.clone(), article_id.clone());
+        Self::load_article_tips(&env, &article_id, page_indices(&env, count, start, limit, false))
+    }
+
+    /// Get a page of an article's tips, newest first
+    /// `start` counts back from the most recent tip
+    pub fn get_latest_article_tips(env: Env, article_id: Symbol, start: u32, limit: u32) -> Vec<SimpleTip> {
+        let count = Self::get_article_tip_count(env.clone(), article_id.clone());
+        Self::load_article_tips(&env, &article_id, page_indices(&env, count, start, limit, true))
+    }
+
+    /// Get the number of tips stored for an article
+    /// Includes legacy tips that have not been migrated yet
+    pub fn get_article_tip_count(env: Env, article_id: Symbol) -> u32 {
+        if let Some(count) = env.storage().persistent().get(&DataKey::ArticleTipCount(article_id.clone())) {
+            return count;
         }
-        
-        // Track cumulative tips for statistics (not balances)
-        let current_total: i128 = env.storage()
+        env.storage()
             .persistent()
-            .get(&DataKey::ArticleTotalTips(article_id.clone()))
-            .unwrap_or(0);
-        
+            .get::<_, Vec<LegacySimpleTip>>(&LegacyDataKey::ArticleTips(article_id))
+            .map(|legacy_tips| legacy_tips.len())
+            .unwrap_or(0)
+    }
+    
+    /// Get author's balance of the native token
+    pub fn get_balance(env: Env, author: Address) -> Result<i128, TippingError> {
+        let native_token = Self::get_native_token(env.clone())?;
+        let native_client = token::TokenClient::new(&env, &native_token);
+        Ok(native_client.balance(&author))
+    }
+
+    /// Get the native token contract configured on initialize
+    pub fn get_native_token(env: Env) -> Result<Address, TippingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NativeToken)
+            .ok_or(TippingError::NotInitialized)
+    }
+    
+    /// Get total tips for an article in a given token (for NFT threshold checking)
+    /// Includes tips sent to the article's highlights; extends the total's TTL
+    pub fn get_article_total_tips(env: Env, article_id: Symbol, token: Address) -> i128 {
+        let key = DataKey::ArticleTotalTips(article_id.clone(), token.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &key);
+
+        // A legacy native-token total counts until it is folded in
+        let legacy_total = match Self::get_native_token(env.clone()) {
+            Ok(native_token) if native_token == token => env.storage()
+                .persistent()
+                .get(&LegacyDataKey::ArticleTotalTips(article_id))
+                .unwrap_or(0),
+            _ => 0,
+        };
+        total + legacy_total
+    }
+
+    /// Get the part of an article's total tipped to the article itself
+    pub fn get_article_direct_total(env: Env, article_id: Symbol, token: Address) -> i128 {
+        Self::get_article_total_tips(env.clone(), article_id.clone(), token.clone())
+            - Self::get_article_highlight_total(env, article_id, token)
+    }
+
+    /// Get the part of an article's total tipped to its highlights
+    pub fn get_article_highlight_total(env: Env, article_id: Symbol, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::ArticleTotalTips(article_id.clone()), &(current_total + amount));
-        
-        // Get and increment tip counter
-        let tip_counter: u64 = env.storage()
-            .persistent()
-            .get(&DataKey::TipCounter)
-            .unwrap_or(0);
-        
-        let new_tip_id = tip_counter + 1;
-        env.storage().persistent().set(&DataKey::TipCounter, &new_tip_id);
-        
-        // Store tip data
-        let tip = SimpleTip {
-            tipper: tipper.clone(),
-            amount,
-            timestamp: env.ledger().timestamp(),
-        };
-        
-        // Get existing tips for article
-        let mut article_tips: Vec<SimpleTip> = env.storage()
+            .get(&DataKey::ArticleHighlightTotal(article_id, token))
+            .unwrap_or(0)
+    }
+    
+    /// Check if article has reached NFT minting threshold in a given token
+    pub fn is_nft_eligible(env: Env, article_id: Symbol, token: Address, threshold: i128) -> bool {
+        let total_tips = Self::get_article_total_tips(env, article_id, token);
+        total_tips >= threshold
+    }
+    
+    /// Withdraw is no longer needed - transfers happen immediately
+    /// Keeping for backwards compatibility, returns 0
+    pub fn withdraw_earnings(_env: Env, author: Address) -> i128 {
+        author.require_auth();
+        // Transfers happen immediately in tip_article
+        // This function is deprecated but kept for backwards compatibility
+        0
+    }
+    
+    /// Get an author's lifetime earnings, broken down by token
+    pub fn get_author_stats(env: Env, author: Address) -> AuthorStats {
+        env.storage()
             .persistent()
-            .get(&DataKey::ArticleTips(article_id.clone()))
-            .unwrap_or(vec![&env]);
-        
-        article_tips.push_back(tip);
-        
+            .get(&DataKey::AuthorStats(author))
+            .unwrap_or(AuthorStats {
+                tip_count: 0,
+                first_tip_at: 0,
+                last_tip_at: 0,
+                tokens: vec![&env],
+            })
+    }
+    
+    /// Get total tips volume for a token
+    pub fn get_total_volume(env: Env, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::ArticleTips(article_id.clone()), &article_tips);
+            .get(&DataKey::TotalVolume(token))
+            .unwrap_or(0)
+    }
+    
+    // ========== FEE TREASURY ==========
+    // Platform fees stay in the contract, accrued per token, until a
+    // treasurer withdraws them. Fees on tips the contract already holds
+    // (pending and escrowed tips) therefore need no transfer at all.
 
-        // Update article total tips (for NFT threshold checking)
-        // TODO: Remove this duplicate update in next contract deployment
-        let current_article_total: i128 = env.storage()
-            .persistent()
-            .get(&DataKey::ArticleTotalTips(article_id.clone()))
-            .unwrap_or(0);
+    /// Get the platform address, which co-signs registrations and escrow claims
+    pub fn get_platform_address(env: Env) -> Result<Address, TippingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PlatformAddress)
+            .ok_or(TippingError::NotInitialized)
+    }
+
+    /// Replace the platform address (owner only)
+    #[only_owner]
+    pub fn set_platform_address(env: Env, new_address: Address) -> Result<(), TippingError> {
+        let old_address = Self::get_platform_address(env.clone())?;
+        env.storage().instance().set(&DataKey::PlatformAddress, &new_address);
+        Self::extend_instance(&env);
 
+        PlatformAddressUpdatedEvent { old_address, new_address }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the platform fees held by the contract in a token
+    pub fn get_accrued_fees(env: Env, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::ArticleTotalTips(article_id.clone()), &(current_article_total + amount));
+            .get(&DataKey::AccruedFees(token))
+            .unwrap_or(0)
+    }
 
-        // Update total volume
-        let total_volume: i128 = env.storage()
-            .persistent()
-            .get(&DataKey::TotalVolume)
-            .unwrap_or(0);
-        
+    /// Get the platform fees withdrawn in a token over the contract's lifetime
+    pub fn get_withdrawn_fees(env: Env, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::TotalVolume, &(total_volume + amount));
-        
-        // Create receipt
-        TipReceipt {
-            tip_id: new_tip_id,
-            amount_sent: amount,
-            author_received: author_share,
-            platform_fee,
-            timestamp: env.ledger().timestamp(),
+            .get(&DataKey::WithdrawnFees(token))
+            .unwrap_or(0)
+    }
+
+    /// Send accrued platform fees to `to` (treasurer role)
+    #[when_not_paused]
+    #[only_role(caller, "treasurer")]
+    pub fn withdraw_fees(
+        env: Env,
+        caller: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), TippingError> {
+        if amount <= 0 {
+            return Err(TippingError::InvalidFeeWithdrawal);
+        }
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        if amount > accrued {
+            return Err(TippingError::InsufficientFees);
+        }
+
+        let accrued_key = DataKey::AccruedFees(token.clone());
+        let withdrawn_key = DataKey::WithdrawnFees(token.clone());
+        let withdrawn = Self::get_withdrawn_fees(env.clone(), token.clone());
+        env.storage().persistent().set(&accrued_key, &(accrued - amount));
+        env.storage().persistent().set(&withdrawn_key, &(withdrawn + amount));
+        Self::extend_persistent(&env, &accrued_key);
+        Self::extend_persistent(&env, &withdrawn_key);
+
+        token::TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        FeesWithdrawnEvent { token, caller, to, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== FEE CHANGES ==========
+    // The global fee only moves through a timelocked proposal, so authors get
+    // notice, and the timelock itself can only be tightened. A due proposal is
+    // charged from its effective ledger onwards: the first tip or charge after
+    // it settles the change and emits FeeUpdatedEvent, unless
+    // activate_fee_change got there first. Reads report the new fee as soon
+    // as it is due, possibly before the event.
+
+    /// Get the global platform fee in effect at the current ledger
+    pub fn get_platform_fee(env: Env) -> u32 {
+        Self::extend_instance(&env);
+        Self::base_fee_bps(&env)
+    }
+
+    /// Get the proposed fee change, if one is waiting
+    pub fn get_pending_fee_change(env: Env) -> Option<FeeChange> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingFeeChange)
+    }
+
+    /// Propose a new platform fee from `effective_ledger` (fee_manager role).
+    /// Replaces any earlier proposal that has not taken effect.
+    #[only_role(caller, "fee_manager")]
+    pub fn propose_fee_change(
+        env: Env,
+        caller: Address,
+        new_fee_bps: u32,
+        effective_ledger: u32,
+    ) -> Result<(), TippingError> {
+        if new_fee_bps > MAX_FEE_BPS {
+            return Err(TippingError::FeeTooHigh);
+        }
+
+        // Settle a due proposal first so the step is measured from the live fee
+        Self::settle_fee_change(&env)?;
+
+        let policy = Self::get_fee_change_policy(env.clone());
+        let old_fee_bps = Self::base_fee_bps(&env);
+        if effective_ledger < env.ledger().sequence().saturating_add(policy.delay_ledgers)
+            || old_fee_bps.abs_diff(new_fee_bps) > policy.max_step_bps
+        {
+            return Err(TippingError::InvalidFeeChange);
         }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingFeeChange, &FeeChange { new_fee_bps, effective_ledger });
+
+        FeeChangeProposedEvent { old_fee_bps, new_fee_bps, effective_ledger }.publish(&env);
+
+        Ok(())
     }
-    
-    /// Get all tips for an article
-    pub fn get_article_tips(env: Env, article_id: Symbol) -> Vec<SimpleTip> {
+
+    /// Withdraw the proposed fee change before it takes effect (fee_manager role)
+    #[only_role(caller, "fee_manager")]
+    pub fn cancel_fee_change(env: Env, caller: Address) -> Result<(), TippingError> {
+        let change = Self::get_pending_fee_change(env.clone())
+            .filter(|change| change.effective_ledger > env.ledger().sequence())
+            .ok_or(TippingError::FeeChangeNotFound)?;
+        env.storage().instance().remove(&DataKey::PendingFeeChange);
+
+        FeeChangeCancelledEvent {
+            new_fee_bps: change.new_fee_bps,
+            effective_ledger: change.effective_ledger,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Record a fee change whose effective ledger has passed. Anyone can call.
+    #[when_not_paused]
+    pub fn activate_fee_change(env: Env) -> Result<(), TippingError> {
+        Self::apply_fee_change(&env)
+    }
+
+    /// Get the fee change timelock and step limit
+    pub fn get_fee_change_policy(env: Env) -> FeeChangePolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeChangePolicy)
+            .unwrap_or(FeeChangePolicy {
+                delay_ledgers: DEFAULT_FEE_CHANGE_DELAY,
+                max_step_bps: DEFAULT_MAX_FEE_STEP_BPS,
+            })
+    }
+
+    /// Tighten the fee change timelock and step limit (owner only).
+    /// The delay can only grow and the step only shrink, so the policy can't
+    /// be loosened to push a fee change through without notice.
+    #[only_owner]
+    pub fn set_fee_change_policy(
+        env: Env,
+        delay_ledgers: u32,
+        max_step_bps: u32,
+    ) -> Result<(), TippingError> {
+        let policy = Self::get_fee_change_policy(env.clone());
+        if delay_ledgers < policy.delay_ledgers || max_step_bps == 0 || max_step_bps > policy.max_step_bps {
+            return Err(TippingError::InvalidFeeChangePolicy);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeChangePolicy, &FeeChangePolicy { delay_ledgers, max_step_bps });
+
+        FeeChangePolicyUpdatedEvent { delay_ledgers, max_step_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== FEE SCHEDULE ==========
+    // An author's fee is their override if set, else the highest volume tier
+    // they qualify for, else the platform fee. Tiers count volume rather than
+    // tips so they cannot be reached with a stream of minimum tips. Overrides and tiers are
+    // discounts: they never exceed the timelocked platform fee. An active
+    // holiday caps the result.
+
+    /// Quote the fee and net amount for tipping `author` at the current ledger
+    pub fn quote_tip(env: Env, author: Address, amount: i128) -> FeeQuote {
+        let fee_bps = Self::fee_bps_for(&env, &author);
+        let fee = (amount * fee_bps as i128) / 10_000;
+        FeeQuote { fee_bps, fee, net: amount - fee }
+    }
+
+    /// Get the volume tiers, lowest threshold first
+    pub fn get_fee_tiers(env: Env) -> Vec<FeeTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeTiers)
+            .unwrap_or(vec![&env])
+    }
+
+    /// Replace the volume tiers (fee_manager role); an empty list removes them
+    #[only_role(caller, "fee_manager")]
+    pub fn set_fee_tiers(env: Env, caller: Address, tiers: Vec<FeeTier>) -> Result<(), TippingError> {
+        if tiers.len() > MAX_FEE_TIERS {
+            return Err(TippingError::InvalidFeeTiers);
+        }
+        let mut previous: Option<i128> = None;
+        for tier in tiers.iter() {
+            if tier.fee_bps > MAX_FEE_BPS {
+                return Err(TippingError::FeeTooHigh);
+            }
+            if tier.min_volume <= 0 || previous.is_some_and(|min_volume| tier.min_volume <= min_volume) {
+                return Err(TippingError::InvalidFeeTiers);
+            }
+            previous = Some(tier.min_volume);
+        }
+
+        if tiers.is_empty() {
+            env.storage().instance().remove(&DataKey::FeeTiers);
+        } else {
+            env.storage().instance().set(&DataKey::FeeTiers, &tiers);
+        }
+
+        FeeTiersUpdatedEvent { tiers }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get an author's negotiated fee, if they have one
+    pub fn get_author_fee(env: Env, author: Address) -> Option<u32> {
         env.storage()
             .persistent()
-            .get(&DataKey::ArticleTips(article_id))
+            .get(&DataKey::AuthorFee(author))
+    }
+
+    /// Set or remove an author's negotiated fee (fee_manager role)
+    #[only_role(caller, "fee_manager")]
+    pub fn set_author_fee(env: Env, caller: Address, author: Address, fee_bps: Option<u32>) -> Result<(), TippingError> {
+        let key = DataKey::AuthorFee(author.clone());
+        match fee_bps {
+            Some(bps) if bps > MAX_FEE_BPS => return Err(TippingError::FeeTooHigh),
+            Some(bps) => {
+                env.storage().persistent().set(&key, &bps);
+                Self::extend_persistent(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        AuthorFeeUpdatedEvent { author, fee_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the promotional fee cap, whether or not it has started
+    pub fn get_fee_holiday(env: Env) -> Option<FeeHoliday> {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeHoliday)
+    }
+
+    /// Schedule a promotional fee cap, replacing any other (fee_manager role)
+    #[only_role(caller, "fee_manager")]
+    pub fn set_fee_holiday(
+        env: Env,
+        caller: Address,
+        start_ledger: u32,
+        end_ledger: u32,
+        fee_bps: u32,
+    ) -> Result<(), TippingError> {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(TippingError::FeeTooHigh);
+        }
+        if start_ledger > end_ledger || end_ledger < env.ledger().sequence() {
+            return Err(TippingError::InvalidFeeHoliday);
+        }
+
+        let holiday = FeeHoliday { start_ledger, end_ledger, fee_bps };
+        env.storage().instance().set(&DataKey::FeeHoliday, &holiday);
+
+        FeeHolidayUpdatedEvent { holiday: Some(holiday) }.publish(&env);
+
+        Ok(())
+    }
+
+    /// End or cancel the promotional fee cap (fee_manager role)
+    #[only_role(caller, "fee_manager")]
+    pub fn clear_fee_holiday(env: Env, caller: Address) -> Result<(), TippingError> {
+        env.storage().instance().remove(&DataKey::FeeHoliday);
+
+        FeeHolidayUpdatedEvent { holiday: None }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== TOKEN ALLOWLIST ==========
+
+    /// Allow a token for tipping with its minimum tip amount (owner only)
+    /// Calling again for an allowed token updates its minimum
+    #[only_owner]
+    pub fn add_allowed_token(env: Env, token: Address, min_amount: i128) -> Result<(), TippingError> {
+        if min_amount <= 0 {
+            return Err(TippingError::InvalidMinimumTip);
+        }
+
+        let mut allowed: Vec<Address> = env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(vec![&env]);
+
+        if !allowed.contains(&token) {
+            allowed.push_back(token.clone());
+            env.storage().instance().set(&DataKey::AllowedTokens, &allowed);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenMinimum(token.clone()), &min_amount);
+
+        TokenAllowedEvent { token, min_amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Stop accepting a token for tipping (owner only)
+    /// Historical totals for the token are kept
+    #[only_owner]
+    pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), TippingError> {
+        let mut allowed: Vec<Address> = env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(vec![&env]);
+
+        let index = allowed.first_index_of(&token).ok_or(TippingError::TokenNotAllowed)?;
+        allowed.remove(index);
+
+        env.storage().instance().set(&DataKey::AllowedTokens, &allowed);
+        env.storage().instance().remove(&DataKey::TokenMinimum(token.clone()));
+
+        TokenRemovedEvent { token }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get all tokens currently accepted for tipping
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
             .unwrap_or(vec![&env])
     }
-    
-    /// Get author's XLM balance from the native token
-    pub fn get_balance(env: Env, author: Address) -> i128 {
-        let xlm_address = Address::from_string(&String::from_str(&env, XLM_TOKEN_ADDRESS));
-        let xlm_client = token::TokenClient::new(&env, &xlm_address);
-        xlm_client.balance(&author)
+
+    /// Check if a token is accepted for tipping
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        env.storage().instance().has(&DataKey::TokenMinimum(token))
     }
-    
-    /// Get total tips for an article (for NFT threshold checking)
-    pub fn get_article_total_tips(env: Env, article_id: Symbol) -> i128 {
+
+    /// Get the minimum tip amount for an allowed token
+    pub fn get_min_tip(env: Env, token: Address) -> Result<i128, TippingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenMinimum(token))
+            .ok_or(TippingError::TokenNotAllowed)
+    }
+
+    /// Tip a highlight directly (same flow as tip_article)
+    #[when_not_paused]
+    pub fn tip_highlight_direct(
+        env: Env,
+        tipper: Address,
+        highlight_id: String,
+        article_id: Symbol,
+        token: Address,
+        amount: i128,
+    ) -> Result<TipReceipt, TippingError> {
+        tipper.require_auth();
+        Self::execute_tip(&env, &tipper, article_id, Some(highlight_id), token, amount, None)
+    }
+
+    /// Get a page of a highlight's tips, oldest first
+    /// `limit` is capped at MAX_PAGE_SIZE
+    pub fn get_highlight_tips(env: Env, highlight_id: String, start: u32, limit: u32) -> Vec<HighlightTip> {
+        let count = Self::get_highlight_tip_count(env.clone(), highlight_id.clone());
+        Self::load_highlight_tips(&env, &highlight_id, page_indices(&env, count, start, limit, false))
+    }
+
+    /// Get a page of a highlight's tips, newest first
+    /// `start` counts back from the most recent tip
+    pub fn get_latest_highlight_tips(env: Env, highlight_id: String, start: u32, limit: u32) -> Vec<HighlightTip> {
+        let count = Self::get_highlight_tip_count(env.clone(), highlight_id.clone());
+        Self::load_highlight_tips(&env, &highlight_id, page_indices(&env, count, start, limit, true))
+    }
+
+    /// Get the number of tips stored for a highlight
+    /// Includes legacy tips that have not been migrated yet
+    pub fn get_highlight_tip_count(env: Env, highlight_id: String) -> u32 {
+        if let Some(count) = env.storage().persistent().get(&DataKey::HighlightTipCount(highlight_id.clone())) {
+            return count;
+        }
         env.storage()
             .persistent()
-            .get(&DataKey::ArticleTotalTips(article_id))
+            .get::<_, Vec<LegacyHighlightTip>>(&LegacyDataKey::HighlightTips(highlight_id))
+            .map(|legacy_tips| legacy_tips.len())
             .unwrap_or(0)
     }
-    
-    /// Check if article has reached NFT minting threshold
-    pub fn is_nft_eligible(env: Env, article_id: Symbol, threshold: i128) -> bool {
-        let total_tips = Self::get_article_total_tips(env, article_id);
-        total_tips >= threshold
+
+    // ========== TIP LIMITS ==========
+    // Per-tipper usage lives in temporary storage, so it expires on its own
+    // once a window or day has passed. The daily cap rolls forward in
+    // SPEND_BUCKET_LEDGERS steps.
+
+    /// Get the anti-spam limits for a token, if any
+    pub fn get_tip_limits(env: Env, token: Address) -> Option<TipLimits> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TipLimits(token))
     }
-    
-    /// Withdraw is no longer needed - transfers happen immediately
-    /// Keeping for backwards compatibility, returns 0
-    pub fn withdraw_earnings(_env: Env, author: Address) -> i128 {
+
+    /// Set or clear (`None`) the anti-spam limits for a token (owner only)
+    #[only_owner]
+    pub fn set_tip_limits(env: Env, token: Address, limits: Option<TipLimits>) -> Result<(), TippingError> {
+        let key = DataKey::TipLimits(token.clone());
+        match &limits {
+            Some(limits) => {
+                let window_ok = limits.max_tips_per_window == 0
+                    || (limits.window_ledgers > 0 && limits.window_ledgers <= env.storage().max_ttl());
+                if !window_ok || limits.max_tip < 0 || limits.daily_cap < 0 {
+                    return Err(TippingError::InvalidTipLimits);
+                }
+                env.storage().instance().set(&key, limits);
+            }
+            None => env.storage().instance().remove(&key),
+        }
+        Self::extend_instance(&env);
+
+        TipLimitsUpdatedEvent { token, limits }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get how much a tipper has sent in a token over roughly the last day
+    pub fn get_daily_tipped(env: Env, tipper: Address, token: Address) -> i128 {
+        Self::recent_spend(&env, &tipper, &token)
+            .iter()
+            .map(|spend| spend.amount)
+            .sum()
+    }
+
+    // ========== TIP MESSAGES ==========
+
+    /// Tip an article with a message stored alongside the tip
+    #[when_not_paused]
+    pub fn tip_article_with_message(
+        env: Env,
+        tipper: Address,
+        article_id: Symbol,
+        token: Address,
+        amount: i128,
+        message: TipMessage,
+    ) -> Result<TipReceipt, TippingError> {
+        tipper.require_auth();
+        Self::execute_tip(&env, &tipper, article_id, None, token, amount, Some(message))
+    }
+
+    /// Tip a highlight with a message stored alongside the tip
+    #[when_not_paused]
+    pub fn tip_highlight_with_message(
+        env: Env,
+        tipper: Address,
+        highlight_id: String,
+        article_id: Symbol,
+        token: Address,
+        amount: i128,
+        message: TipMessage,
+    ) -> Result<TipReceipt, TippingError> {
+        tipper.require_auth();
+        Self::execute_tip(&env, &tipper, article_id, Some(highlight_id), token, amount, Some(message))
+    }
+
+    /// Get the message sent with a tip, if any
+    pub fn get_tip_message(env: Env, tip_id: u64) -> Option<TipMessage> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TipMessage(tip_id))
+    }
+
+    /// Accept or refuse public text messages on tips (author only)
+    /// Hashed messages are always accepted
+    #[when_not_paused]
+    pub fn set_public_messages(env: Env, author: Address, enabled: bool) -> Result<(), TippingError> {
         author.require_auth();
-        // Transfers happen immediately in tip_article
-        // This function is deprecated but kept for backwards compatibility
-        0
+
+        let key = DataKey::PublicMessagesOff(author.clone());
+        if enabled {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        }
+
+        PublicMessagesUpdatedEvent { author, enabled }.publish(&env);
+
+        Ok(())
     }
-    
-    /// Get total tips volume
-    pub fn get_total_volume(env: Env) -> i128 {
+
+    /// Check if an author accepts public text messages
+    pub fn accepts_public_messages(env: Env, author: Address) -> bool {
+        !env.storage()
+            .persistent()
+            .has(&DataKey::PublicMessagesOff(author))
+    }
+
+    // ========== TIPPER HISTORY ==========
+
+    /// Look up a tip by id
+    pub fn get_tip(env: Env, tip_id: u64) -> Option<TipRecord> {
+        env.storage().persistent().get(&DataKey::Tip(tip_id))
+    }
+
+    /// Get a page of the tips a wallet has sent, oldest first
+    /// `limit` is capped at MAX_PAGE_SIZE
+    pub fn get_tipper_tips(env: Env, tipper: Address, start: u32, limit: u32) -> Vec<TipRecord> {
+        Self::tipper_tips_page(&env, &tipper, start, limit, false)
+    }
+
+    /// Get a page of the tips a wallet has sent, newest first
+    /// `start` counts back from the most recent tip
+    pub fn get_latest_tipper_tips(env: Env, tipper: Address, start: u32, limit: u32) -> Vec<TipRecord> {
+        Self::tipper_tips_page(&env, &tipper, start, limit, true)
+    }
+
+    /// Get a wallet's tip count, distinct authors supported and total given per token
+    pub fn get_tipper_stats(env: Env, tipper: Address) -> TipperStats {
         env.storage()
             .persistent()
-            .get(&DataKey::TotalVolume)
-            .unwrap_or(0)
+            .get(&DataKey::TipperStats(tipper))
+            .unwrap_or(TipperStats {
+                tip_count: 0,
+                authors_supported: 0,
+                given: vec![&env],
+            })
     }
-    
-    /// Update platform fee (admin only)
-    pub fn update_fee(env: Env, admin: Address, new_fee_bps: u32) {
-        admin.require_auth();
 
-        let stored_admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+    /// Get a page of an author's supporters in the order they first tipped
+    /// `limit` is capped at MAX_PAGE_SIZE; see get_top_supporters for a ranking
+    pub fn get_supporters(env: Env, author: Address, start: u32, limit: u32) -> Vec<Supporter> {
+        let count = Self::get_supporter_count(env.clone(), author.clone());
+        let mut supporters = vec![&env];
+        for index in page_indices(&env, count, start, limit, false) {
+            if let Some(tipper) = env.storage()
+                .persistent()
+                .get::<DataKey, Address>(&DataKey::Supporter(author.clone(), index)) {
+                let given = env.storage()
+                    .persistent()
+                    .get(&DataKey::SupporterGiven(author.clone(), tipper.clone()))
+                    .unwrap_or(vec![&env]);
+                supporters.push_back(Supporter { tipper, given });
+            }
+        }
+        supporters
+    }
+
+    /// Get the number of distinct wallets that have tipped an author
+    pub fn get_supporter_count(env: Env, author: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SupporterCount(author))
+            .unwrap_or(0)
+    }
+
+    /// Get an author's biggest supporters in a token, largest total first
+    /// At most MAX_TOP_SUPPORTERS; earlier supporters rank ahead on ties
+    pub fn get_top_supporters(env: Env, author: Address, token: Address) -> Vec<RankedSupporter> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TopSupporters(author, token))
+            .unwrap_or(vec![&env])
+    }
+
+    // ========== TIP HISTORY MIGRATION ==========
+    // A legacy vector is moved MAX_MIGRATION_PAGE tips per call. The first touch,
+    // including a new tip, reserves indices for the whole vector, so new tips
+    // append behind it without waiting. Reads fall back to the vector for
+    // tips not moved yet.
+
+    /// Move the next page of an article's legacy tip vector into indexed entries
+    /// Permissionless; returns the number of legacy tips still to move
+    #[when_not_paused]
+    pub fn migrate_article_tips(env: Env, article_id: Symbol) -> Result<u32, TippingError> {
+        Self::migrate_legacy_article_tips(&env, &article_id, MAX_MIGRATION_PAGE)
+    }
+
+    /// Move the next page of a highlight's legacy tip vector into indexed entries
+    /// Permissionless; returns the number of legacy tips still to move
+    #[when_not_paused]
+    pub fn migrate_highlight_tips(env: Env, highlight_id: String) -> Result<u32, TippingError> {
+        Self::migrate_legacy_highlight_tips(&env, &highlight_id, MAX_MIGRATION_PAGE)
+    }
+
+    /// Rebuild article totals from their stored tip history (owner only)
+    /// Repairs totals inflated by the original deployment, which added every
+    /// article tip twice. Scans up to `budget` tips per call (capped at
+    /// MAX_PAGE_SIZE) across the listed articles, keeping partial sums
+    /// between calls; an article's totals are rewritten once its scan ends.
+    /// Returns the articles still to finish, to pass to the next call.
+    #[only_owner]
+    pub fn recompute_article_totals(env: Env, article_ids: Vec<Symbol>, budget: u32) -> Result<Vec<Symbol>, TippingError> {
+        if article_ids.is_empty() {
+            return Err(TippingError::EmptyBatch);
+        }
+        if article_ids.len() > MAX_BATCH_SIZE {
+            return Err(TippingError::BatchTooLarge);
+        }
+
+        let mut budget = budget.min(MAX_PAGE_SIZE);
+        let mut pending = vec![&env];
+        for article_id in article_ids.iter() {
+            if !pending.is_empty() || !Self::recompute_article_total(&env, &article_id, &mut budget)? {
+                pending.push_back(article_id);
+            }
+        }
+
+        Ok(pending)
+    }
+
+    // ========== UPGRADES ==========
+
+    /// Get the storage layout version; 0 is the original deployment
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+
+    /// Replace the contract code, keeping its id and storage (owner only)
+    /// Call `migrate` afterwards if the new code bumps SCHEMA_VERSION
+    #[only_owner]
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TippingError> {
+        UpgradedEvent { wasm_hash: new_wasm_hash.clone() }.publish(&env);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring contract-wide storage up to SCHEMA_VERSION; returns the version.
+    /// Permissionless and idempotent. Per-article history still moves lazily,
+    /// see TIP HISTORY MIGRATION.
+    pub fn migrate(env: Env) -> Result<u32, TippingError> {
+        if !env.storage().instance().has(&DataKey::PlatformAddress) {
+            return Err(TippingError::NotInitialized);
+        }
+
+        let from_version = Self::get_schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Ok(from_version);
+        }
+
+        if from_version == 0 {
+            Self::migrate_from_v0(&env)?;
+        }
+
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Self::extend_instance(&env);
+
+        MigratedEvent { from_version, to_version: SCHEMA_VERSION }.publish(&env);
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    // ========== ARTICLE REGISTRY ==========
+
+    /// Register an article with its author's payout address
+    /// Requires the author's signature, plus the platform's when co-signing is enabled
+    #[when_not_paused]
+    pub fn register_article(env: Env, author: Address, article_id: Symbol) -> Result<(), TippingError> {
+        author.require_auth();
+
+        if env.storage().instance().get(&DataKey::RegistrationCosign).unwrap_or(false) {
+            let platform_address: Address = env.storage()
+                .instance()
+                .get(&DataKey::PlatformAddress)
+                .ok_or(TippingError::NotInitialized)?;
+            platform_address.require_auth();
+        }
+
+        if env.storage().persistent().has(&DataKey::ArticleAuthor(article_id.clone())) {
+            return Err(TippingError::ArticleAlreadyRegistered);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArticleAuthor(article_id.clone()), &author);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::ArticleAuthor(article_id.clone()));
+
+        ArticleRegisteredEvent { article_id, author }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the registered author of an article
+    pub fn get_article_author(env: Env, article_id: Symbol) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArticleAuthor(article_id))
+    }
+
+    /// Move a registration to the article's real author, e.g. after squatting
+    /// The platform attests the binding and the new author signs; any split
+    /// table set by the previous registrant is dropped
+    pub fn reassign_article(env: Env, article_id: Symbol, new_author: Address) -> Result<(), TippingError> {
+        let platform_address: Address = env.storage()
+            .instance()
+            .get(&DataKey::PlatformAddress)
+            .ok_or(TippingError::NotInitialized)?;
+        platform_address.require_auth();
+        new_author.require_auth();
+
+        let old_author = Self::get_article_author(env.clone(), article_id.clone())
+            .ok_or(TippingError::ArticleNotRegistered)?;
+
+        let key = DataKey::ArticleAuthor(article_id.clone());
+        env.storage().persistent().set(&key, &new_author);
+        env.storage().persistent().remove(&DataKey::ArticleSplits(article_id.clone()));
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &key);
+
+        ArticleReassignedEvent { article_id, old_author, new_author }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get how an article's tips are split between its co-authors
+    /// Without a split table the registered author takes everything; empty if unregistered
+    pub fn get_article_splits(env: Env, article_id: Symbol) -> Vec<SplitShare> {
+        if let Some(splits) = env.storage()
+            .persistent()
+            .get(&DataKey::ArticleSplits(article_id.clone())) {
+            return splits;
+        }
+        match Self::get_article_author(env.clone(), article_id) {
+            Some(author) => vec![&env, SplitShare { recipient: author, share_bps: 10_000 }],
+            None => vec![&env],
+        }
+    }
+
+    /// Replace an article's co-author split table
+    /// Every current member must sign; shares are basis points summing to 10_000
+    #[when_not_paused]
+    pub fn set_article_splits(env: Env, article_id: Symbol, splits: Vec<SplitShare>) -> Result<(), TippingError> {
+        let current = Self::get_article_splits(env.clone(), article_id.clone());
+        if current.is_empty() {
+            return Err(TippingError::ArticleNotRegistered);
+        }
+        for member in current.iter() {
+            member.recipient.require_auth();
+        }
+
+        if splits.is_empty() || splits.len() > MAX_SPLIT_RECIPIENTS {
+            return Err(TippingError::InvalidSplits);
+        }
+        let mut total_bps: u32 = 0;
+        for share in splits.iter() {
+            let duplicates = splits.iter().filter(|other| other.recipient == share.recipient).count();
+            if share.share_bps == 0 || duplicates > 1 {
+                return Err(TippingError::InvalidSplits);
+            }
+            total_bps = total_bps.saturating_add(share.share_bps);
+        }
+        if total_bps != 10_000 {
+            return Err(TippingError::InvalidSplits);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArticleSplits(article_id.clone()), &splits);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::ArticleSplits(article_id.clone()));
+
+        SplitsUpdatedEvent { article_id, splits }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Require the platform to co-sign article registrations (owner only)
+    #[only_owner]
+    pub fn set_registration_cosign(env: Env, required: bool) -> Result<(), TippingError> {
+        env.storage()
+            .instance()
+            .set(&DataKey::RegistrationCosign, &required);
+
+        RegistrationCosignEvent { required }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== STORAGE TTL ==========
+
+    /// Extend the TTL of an article's registration, tip count and per-token totals
+    /// Covers every token the article was tipped in, including removed ones.
+    /// Permissionless so a keeper can keep popular articles from being archived
+    pub fn bump_article(env: Env, article_id: Symbol) {
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::ArticleAuthor(article_id.clone()));
+        Self::extend_persistent(&env, &DataKey::ArticleTipCount(article_id.clone()));
+        Self::extend_persistent(&env, &DataKey::ArticleTokens(article_id.clone()));
+        for token in Self::article_tokens(&env, &article_id) {
+            Self::extend_persistent(&env, &DataKey::ArticleTotalTips(article_id.clone(), token.clone()));
+            Self::extend_persistent(&env, &DataKey::ArticleHighlightTotal(article_id.clone(), token));
+        }
+    }
+
+    /// Extend the TTL of a page of an article's tip history
+    /// `limit` is capped at MAX_PAGE_SIZE
+    pub fn bump_article_tips(env: Env, article_id: Symbol, start: u32, limit: u32) {
+        let count = Self::get_article_tip_count(env.clone(), article_id.clone());
+        Self::extend_instance(&env);
+        for index in page_indices(&env, count, start, limit, false) {
+            Self::extend_persistent(&env, &DataKey::ArticleTip(article_id.clone(), index));
+        }
+    }
+
+    /// Extend the TTL of a page of a highlight's tip history and its count
+    /// `limit` is capped at MAX_PAGE_SIZE
+    pub fn bump_highlight_tips(env: Env, highlight_id: String, start: u32, limit: u32) {
+        let count = Self::get_highlight_tip_count(env.clone(), highlight_id.clone());
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::HighlightTipCount(highlight_id.clone()));
+        for index in page_indices(&env, count, start, limit, false) {
+            Self::extend_persistent(&env, &DataKey::HighlightTip(highlight_id.clone(), index));
+        }
+    }
+
+    /// Extend the TTL of a page of a wallet's tip records and its stats
+    /// Each tip holds two entries, so `limit` is capped at MAX_PAGE_SIZE / 2
+    pub fn bump_tipper_tips(env: Env, tipper: Address, start: u32, limit: u32) {
+        let count = Self::get_tipper_stats(env.clone(), tipper.clone()).tip_count;
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::TipperStats(tipper.clone()));
+        for index in page_indices(&env, count, start, limit.min(MAX_PAGE_SIZE / 2), false) {
+            let index_key = DataKey::TipperTip(tipper.clone(), index);
+            if let Some(tip_id) = env.storage().persistent().get::<_, u64>(&index_key) {
+                Self::extend_persistent(&env, &index_key);
+                Self::extend_persistent(&env, &DataKey::Tip(tip_id));
+            }
+        }
+    }
+
+    /// Get the TTL bump policy
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                threshold: DEFAULT_TTL_THRESHOLD,
+                extend_to: DEFAULT_TTL_EXTEND_TO,
+            })
+    }
+
+    /// Update the TTL bump policy (owner only)
+    #[only_owner]
+    pub fn set_ttl_config(env: Env, threshold: u32, extend_to: u32) -> Result<(), TippingError> {
+        if threshold >= extend_to || extend_to > env.storage().max_ttl() {
+            return Err(TippingError::InvalidTtlConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TtlConfig, &TtlConfig { threshold, extend_to });
+        Self::extend_instance(&env);
+
+        TtlConfigUpdatedEvent { threshold, extend_to }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== PAUSABLE PATTERN (OZ) ==========
+    // Pausing blocks every entrypoint that moves funds or writes user state
+    // (#[when_not_paused]), including withdraw_fees. Recovery stays open:
+    // owner and role settings, reverse_pending_tip, reassign_article,
+    // recompute_article_totals, upgrade and migrate, ownership and role
+    // management, and the TTL bumps that keep storage from being archived.
+
+    /// Check if contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        pausable::paused(&env)
+    }
+
+    /// Pause the contract (pauser role)
+    #[only_role(caller, "pauser")]
+    pub fn pause(env: Env, caller: Address) -> Result<(), TippingError> {
+        pausable::pause(&env);
+        PauseChangedEvent { caller, paused: true }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Unpause the contract (pauser role)
+    #[only_role(caller, "pauser")]
+    pub fn unpause(env: Env, caller: Address) -> Result<(), TippingError> {
+        pausable::unpause(&env);
+        PauseChangedEvent { caller, paused: false }.publish(&env);
+
+        Ok(())
+    }
+
+    // ========== ARWEAVE-ENABLED TIPPING ==========
+
+    /// Tip an article with Arweave reference
+    /// Unlike plain tips, these are never escrowed: the article must have a registered author
+    #[when_not_paused]
+    pub fn tip_article_with_arweave(
+        env: Env,
+        tipper: Address,
+        article_id: Symbol,
+        token: Address,
+        amount: i128,
+        arweave_tx_id: String,
+    ) -> Result<TipReceipt, TippingError> {
+        // Execute normal tip
+        let receipt = Self::tip_article(env.clone(), tipper.clone(), article_id.clone(), token.clone(), amount)?;
+        let author = Self::get_article_author(env.clone(), article_id.clone())
+            .ok_or(TippingError::ArticleNotRegistered)?;
+
+        // Emit event with Arweave TX ID
+        ArweaveTipEvent {
+            article_id,
+            tip_id: receipt.tip_id,
+            highlight_id: None,
+            tipper,
+            author,
+            token,
+            amount,
+            arweave_tx_id,
+        }
+        .publish(&env);
+
+        Ok(receipt)
+    }
+
+    /// Tip a highlight with Arweave reference
+    #[when_not_paused]
+    pub fn tip_highlight_with_arweave(
+        env: Env,
+        tipper: Address,
+        highlight_id: String,
+        article_id: Symbol,
+        token: Address,
+        amount: i128,
+        arweave_tx_id: String,
+    ) -> Result<TipReceipt, TippingError> {
+        // Execute normal highlight tip
+        let receipt = Self::tip_highlight_direct(
+            env.clone(),
+            tipper.clone(),
+            highlight_id.clone(),
+            article_id.clone(),
+            token.clone(),
+            amount
+        )?;
+        let author = Self::get_article_author(env.clone(), article_id.clone())
+            .ok_or(TippingError::ArticleNotRegistered)?;
+
+        // Emit event with Arweave TX ID
+        ArweaveTipEvent {
+            article_id,
+            tip_id: receipt.tip_id,
+            highlight_id: Some(highlight_id),
+            tipper,
+            author,
+            token,
+            amount,
+            arweave_tx_id,
+        }
+        .publish(&env);
+
+        Ok(receipt)
+    }
+
+    // ========== BATCH TIPPING ==========
+
+    /// Tip several articles and highlights with one signature
+    /// Every item must pass the same checks as a single tip; if any fails, no tip is made
+    #[when_not_paused]
+    pub fn tip_batch(env: Env, tipper: Address, items: Vec<TipItem>) -> Result<Vec<TipReceipt>, TippingError> {
+        tipper.require_auth();
+
+        if items.is_empty() {
+            return Err(TippingError::EmptyBatch);
+        }
+        if items.len() > MAX_BATCH_SIZE {
+            return Err(TippingError::BatchTooLarge);
+        }
+
+        let mut receipts = vec![&env];
+        for item in items {
+            let receipt = Self::execute_tip(
+                &env,
+                &tipper,
+                item.article_id.clone(),
+                item.highlight_id.clone(),
+                item.token.clone(),
+                item.amount,
+                Self::batch_item_message(&item)?
+            )?;
+
+            // Same Arweave events as the single-tip variants
+            if let Some(arweave_tx_id) = item.arweave_tx_id {
+                let author = Self::get_article_author(env.clone(), item.article_id.clone())
+                    .ok_or(TippingError::ArticleNotRegistered)?;
+                ArweaveTipEvent {
+                    article_id: item.article_id,
+                    tip_id: receipt.tip_id,
+                    highlight_id: item.highlight_id,
+                    tipper: tipper.clone(),
+                    author,
+                    token: item.token,
+                    amount: item.amount,
+                    arweave_tx_id,
+                }
+                .publish(&env);
+            }
+
+            receipts.push_back(receipt);
+        }
+
+        Ok(receipts)
+    }
+
+    // ========== ESCROW ==========
+
+    /// Pay an article's escrowed tips out to its author
+    /// The platform attests the article→author binding by co-signing; an unregistered
+    /// article is registered to `author`. Settles up to MAX_PAGE_SIZE tips per call
+    /// and returns how many were settled, so large escrows take several calls.
+    #[when_not_paused]
+    pub fn claim_escrow(env: Env, author: Address, article_id: Symbol) -> Result<u32, TippingError> {
+        author.require_auth();
+
+        let platform_address: Address = env.storage()
+            .instance()
+            .get(&DataKey::PlatformAddress)
+            .ok_or(TippingError::NotInitialized)?;
+        platform_address.require_auth();
+
+        match Self::get_article_author(env.clone(), article_id.clone()) {
+            Some(registered) if registered != author => return Err(TippingError::Unauthorized),
+            Some(_) => {}
+            None => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArticleAuthor(article_id.clone()), &author);
+                ArticleRegisteredEvent { article_id: article_id.clone(), author: author.clone() }.publish(&env);
+            }
+        }
+
+        let count: u32 = env.storage()
+            .persistent()
+            .get(&DataKey::EscrowCount(article_id.clone()))
+            .unwrap_or(0);
+        let mut index: u32 = env.storage()
+            .persistent()
+            .get(&DataKey::EscrowHead(article_id.clone()))
+            .unwrap_or(0);
+
+        let contract = env.current_contract_address();
+        let mut settled = 0;
+        while index < count && settled < MAX_PAGE_SIZE {
+            if let Some(escrowed) = Self::take_escrowed_tip(&env, &article_id, index) {
+                Self::settle_tip(&env, &contract, &author, TipRequest {
+                    tip_id: escrowed.tip_id,
+                    tipper: escrowed.tipper,
+                    article_id: article_id.clone(),
+                    highlight_id: escrowed.highlight_id,
+                    token: escrowed.token,
+                    amount: escrowed.amount,
+                })?;
+                settled += 1;
+            }
+            index += 1;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowHead(article_id.clone()), &index);
+        Self::extend_instance(&env);
+        Self::extend_persistent(&env, &DataKey::ArticleAuthor(article_id.clone()));
+        Self::extend_persistent(&env, &DataKey::EscrowHead(article_id.clone()));
+
+        EscrowClaimedEvent { article_id, author, settled }.publish(&env);
+
+        Ok(settled)
+    }
+
+    /// Return an unclaimed escrowed tip to its tipper
+    /// Permissionless once ESCROW_EXPIRY_SECS have passed since the tip
+    #[when_not_paused]
+    pub fn refund_escrow(env: Env, article_id: Symbol, index: u32) -> Result<(), TippingError> {
+        let escrowed: EscrowedTip = env.storage()
+            .persistent()
+            .get(&DataKey::EscrowTip(article_id.clone(), index))
+            .ok_or(TippingError::EscrowNotFound)?;
+
+        if env.ledger().timestamp() < escrowed.timestamp + ESCROW_EXPIRY_SECS {
+            return Err(TippingError::EscrowNotExpired);
+        }
+
+        Self::take_escrowed_tip(&env, &article_id, index);
+        token::TokenClient::new(&env, &escrowed.token)
+            .transfer(&env.current_contract_address(), &escrowed.tipper, &escrowed.amount);
+
+        EscrowRefundedEvent {
+            article_id,
+            tip_id: escrowed.tip_id,
+            tipper: escrowed.tipper,
+            token: escrowed.token,
+            amount: escrowed.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a page of the tips an article holds in escrow, oldest first
+    /// Claimed and refunded tips are skipped, so a page may hold fewer than `limit`
+    pub fn get_escrowed_tips(env: Env, article_id: Symbol, start: u32, limit: u32) -> Vec<EscrowedTip> {
+        let count: u32 = env.storage()
+            .persistent()
+            .get(&DataKey::EscrowCount(article_id.clone()))
+            .unwrap_or(0);
+        let mut tips = vec![&env];
+        for index in page_indices(&env, count, start, limit, false) {
+            if let Some(tip) = env.storage().persistent().get(&DataKey::EscrowTip(article_id.clone(), index)) {
+                tips.push_back(tip);
+            }
+        }
+        tips
+    }
+
+    /// Get the amount of a token an article currently holds in escrow
+    pub fn get_escrow_total(env: Env, article_id: Symbol, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowTotal(article_id, token))
+            .unwrap_or(0)
+    }
+
+    // ========== PENDING TIPS ==========
+
+    /// Number of ledgers new tips stay refundable before they can be released
+    /// 0 (the default) pays tips out immediately
+    pub fn get_pending_window(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingWindow)
+            .unwrap_or(0)
+    }
+
+    /// Set the refund window for new tips (owner only), at most MAX_PENDING_WINDOW ledgers
+    #[only_owner]
+    pub fn set_pending_window(env: Env, ledgers: u32) -> Result<(), TippingError> {
+        if ledgers > MAX_PENDING_WINDOW {
+            return Err(TippingError::InvalidPendingWindow);
+        }
+
+        env.storage().instance().set(&DataKey::PendingWindow, &ledgers);
+        Self::extend_instance(&env);
+
+        PendingWindowUpdatedEvent { ledgers }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a tip still inside its refund window
+    pub fn get_pending_tip(env: Env, tip_id: u64) -> Option<PendingTip> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingTip(tip_id))
+    }
+
+    /// Take back a pending tip before its window closes (tipper only)
+    #[when_not_paused]
+    pub fn cancel_pending_tip(env: Env, tip_id: u64) -> Result<(), TippingError> {
+        let pending = Self::get_pending_tip(env.clone(), tip_id)
+            .ok_or(TippingError::PendingTipNotFound)?;
+        pending.tipper.require_auth();
+
+        Self::refund_pending_tip(&env, pending, false)
+    }
+
+    /// Return a disputed pending tip to its tipper before its window closes (owner only)
+    #[only_owner]
+    pub fn reverse_pending_tip(env: Env, tip_id: u64) -> Result<(), TippingError> {
+        let pending = Self::get_pending_tip(env.clone(), tip_id)
+            .ok_or(TippingError::PendingTipNotFound)?;
+
+        Self::refund_pending_tip(&env, pending, true)
+    }
+
+    /// Release a pending tip to its author and the platform once its window has closed
+    /// Permissionless so a keeper, the author or the tipper can finalize
+    #[when_not_paused]
+    pub fn finalize_tip(env: Env, tip_id: u64) -> Result<TipReceipt, TippingError> {
+        let pending = Self::get_pending_tip(env.clone(), tip_id)
+            .ok_or(TippingError::PendingTipNotFound)?;
+
+        if env.ledger().sequence() < pending.release_ledger {
+            return Err(TippingError::PendingWindowOpen);
+        }
+
+        let author = Self::get_article_author(env.clone(), pending.article_id.clone())
+            .ok_or(TippingError::ArticleNotRegistered)?;
+        env.storage().persistent().remove(&DataKey::PendingTip(tip_id));
+
+        Self::settle_tip(&env, &env.current_contract_address(), &author, TipRequest {
+            tip_id,
+            tipper: pending.tipper,
+            article_id: pending.article_id,
+            highlight_id: pending.highlight_id,
+            token: pending.token,
+            amount: pending.amount,
+        })
+    }
+
+    // ========== SUBSCRIPTIONS ==========
+
+    /// Subscribe to tip an author `amount` of `token` every `period_secs`, up to `max_periods` times
+    /// `period_secs` must be within MIN_SUBSCRIPTION_PERIOD_SECS..=MAX_SUBSCRIPTION_PERIOD_SECS. The first period is due immediately. The subscriber must separately approve this
+    /// contract to spend the total on `token`; `collect_subscription` pulls from that allowance.
+    #[when_not_paused]
+    pub fn subscribe(
+        env: Env,
+        subscriber: Address,
+        author: Address,
+        token: Address,
+        amount: i128,
+        period_secs: u64,
+        max_periods: u32,
+    ) -> Result<u64, TippingError> {
+        subscriber.require_auth();
+
+        let minimum_tip = Self::get_min_tip(env.clone(), token.clone())?;
+        if amount < minimum_tip {
+            return Err(TippingError::BelowMinimumTip);
+        }
+        if !(MIN_SUBSCRIPTION_PERIOD_SECS..=MAX_SUBSCRIPTION_PERIOD_SECS).contains(&period_secs)
+            || max_periods == 0
+            || subscriber == author
+        {
+            return Err(TippingError::InvalidSubscription);
+        }
+
+        let subscription_id: u64 = env.storage()
+            .persistent()
+            .get(&DataKey::SubscriptionCounter)
+            .unwrap_or(0u64) + 1;
+        env.storage().persistent().set(&DataKey::SubscriptionCounter, &subscription_id);
+
+        let subscription = Subscription {
+            id: subscription_id,
+            subscriber: subscriber.clone(),
+            author: author.clone(),
+            token: token.clone(),
+            amount,
+            period_secs,
+            max_periods,
+            periods_charged: 0,
+            next_charge_at: env.ledger().timestamp(),
+            status: SubscriptionStatus::Active,
+        };
+        Self::save_subscription(&env, &subscription);
+        Self::extend_persistent(&env, &DataKey::SubscriptionCounter);
+
+        SubscriptionCreatedEvent {
+            subscription_id,
+            subscriber,
+            author,
+            token,
+            amount,
+            period_secs,
+            max_periods,
+        }
+        .publish(&env);
+
+        Ok(subscription_id)
+    }
+
+    /// Charge the periods of a subscription that have come due, up to MAX_COLLECT_PERIODS
+    /// Permissionless so a keeper can run it. Stops at the first period the subscriber
+    /// cannot cover, emitting a failure event; that period stays due for a later retry.
+    /// Returns the number of periods charged; call again to catch up on the rest.
+    #[when_not_paused]
+    pub fn collect_subscription(env: Env, subscription_id: u64) -> Result<u32, TippingError> {
+        let mut subscription = Self::get_subscription(env.clone(), subscription_id)
+            .ok_or(TippingError::SubscriptionNotFound)?;
+        if subscription.status != SubscriptionStatus::Active {
+            return Err(TippingError::SubscriptionInactive);
+        }
+        let now = env.ledger().timestamp();
+        if subscription.next_charge_at > now {
+            return Err(TippingError::SubscriptionNotDue);
+        }
+
+        if !env.storage().instance().has(&DataKey::PlatformAddress) {
+            return Err(TippingError::NotInitialized);
+        }
+
+        Self::settle_fee_change(&env)?;
+        let platform_fee_bps = Self::fee_bps_for(&env, &subscription.author);
+        let platform_fee = (subscription.amount * platform_fee_bps as i128) / 10_000;
+        let author_share = subscription.amount - platform_fee;
+
+        let token_client = token::TokenClient::new(&env, &subscription.token);
+        let spender = env.current_contract_address();
+        let mut charged = 0;
+
+        while subscription.next_charge_at <= now
+            && subscription.periods_charged < subscription.max_periods
+            && charged < MAX_COLLECT_PERIODS
+        {
+            let period = subscription.periods_charged + 1;
+
+            // Check up front so a shortfall is reported instead of aborting the call
+            let failure = if token_client.allowance(&subscription.subscriber, &spender) < subscription.amount {
+                Some("insufficient_allowance")
+            } else if token_client.balance(&subscription.subscriber) < subscription.amount {
+                Some("insufficient_balance")
+            } else {
+                None
+            };
+            if let Some(reason) = failure {
+                SubscriptionChargeFailedEvent {
+                    subscription_id,
+                    period,
+                    reason: Symbol::new(&env, reason),
+                }
+                .publish(&env);
+                break;
+            }
+
+            token_client.transfer_from(&spender, &subscription.subscriber, &subscription.author, &author_share);
+            if platform_fee > 0 {
+                token_client.transfer_from(&spender, &subscription.subscriber, &spender, &platform_fee);
+                Self::accrue_fee(&env, &subscription.token, platform_fee);
+            }
+
+            subscription.periods_charged = period;
+            subscription.next_charge_at = subscription.next_charge_at
+                .checked_add(subscription.period_secs)
+                .ok_or(TippingError::InvalidSubscription)?;
+            charged += 1;
+
+            // Indexed like any other tip, without an article
+            Self::record_tip(&env, &TipRecord {
+                tip_id: Self::next_tip_id(&env),
+                article_id: None,
+                highlight_id: None,
+                subscription_id: Some(subscription_id),
+                tipper: subscription.subscriber.clone(),
+                author: subscription.author.clone(),
+                token: subscription.token.clone(),
+                amount: subscription.amount,
+                platform_fee,
+                timestamp: now,
+            });
+
+            SubscriptionChargedEvent {
+                subscription_id,
+                period,
+                token: subscription.token.clone(),
+                gross: subscription.amount,
+                fee: platform_fee,
+                net: author_share,
+            }
+            .publish(&env);
+        }
+
+        if charged > 0 {
+            let total = subscription.amount * charged as i128;
+            let total_volume: i128 = env.storage()
+                .persistent()
+                .get(&DataKey::TotalVolume(subscription.token.clone()))
+                .unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::TotalVolume(subscription.token.clone()), &(total_volume + total));
+
+            for _ in 0..charged {
+                Self::record_author_tip(&env, &subscription.author, &subscription.token, subscription.amount, platform_fee);
+            }
+
+            Self::extend_instance(&env);
+            Self::extend_persistent(&env, &DataKey::TotalVolume(subscription.token.clone()));
+            Self::extend_persistent(&env, &DataKey::AuthorStats(subscription.author.clone()));
+        }
+
+        if subscription.periods_charged == subscription.max_periods {
+            subscription.status = SubscriptionStatus::Completed;
+            SubscriptionStatusEvent { subscription_id, status: subscription.status }.publish(&env);
+        }
+        Self::save_subscription(&env, &subscription);
+
+        Ok(charged)
+    }
+
+    /// Get a subscription by id
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Option<Subscription> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscription(subscription_id))
+    }
+
+    /// Stop charging a subscription until it is resumed (subscriber only)
+    #[when_not_paused]
+    pub fn pause_subscription(env: Env, subscription_id: u64) -> Result<(), TippingError> {
+        Self::set_subscription_status(&env, subscription_id, SubscriptionStatus::Paused)
+    }
+
+    /// Resume a paused subscription (subscriber only)
+    /// Periods missed while paused are not back-charged; at most one falls due on resume
+    #[when_not_paused]
+    pub fn resume_subscription(env: Env, subscription_id: u64) -> Result<(), TippingError> {
+        Self::set_subscription_status(&env, subscription_id, SubscriptionStatus::Active)
+    }
+
+    /// Cancel a subscription for good (subscriber only)
+    #[when_not_paused]
+    pub fn cancel_subscription(env: Env, subscription_id: u64) -> Result<(), TippingError> {
+        Self::set_subscription_status(&env, subscription_id, SubscriptionStatus::Cancelled)
+    }
+}
+
+// ========== OWNERSHIP (OZ) ==========
+// get_owner, two-step transfer_ownership/accept_ownership (the offer
+// expires at `live_until_ledger`) and renounce_ownership. The role admin
+// follows ownership: each step also runs the matching access-control admin
+// transfer, and the standalone admin transfer entrypoints are disabled, so
+// the two can't drift apart. Renouncing freezes the allowlist, upgrades and every other
+// owner-only setting, and roles can no longer be granted or revoked;
+// accounts already holding a role, such as a fee_manager, keep it.
+
+#[default_impl]
+#[contractimpl]
+impl Ownable for TippingContract {
+    fn transfer_ownership(e: &Env, new_owner: Address, live_until_ledger: u32) {
+        ownable::transfer_ownership(e, &new_owner, live_until_ledger);
+        access_control::transfer_admin_role(e, &new_owner, live_until_ledger);
+    }
+
+    fn accept_ownership(e: &Env) {
+        ownable::accept_ownership(e);
+        access_control::accept_admin_transfer(e);
+    }
+
+    fn renounce_ownership(e: &Env) {
+        ownable::renounce_ownership(e);
+        access_control::renounce_admin(e);
+    }
+}
+
+// ========== ROLES (OZ) ==========
+// Routine operations are delegated to roles so the owner key can stay
+// offline: "pauser" pauses and unpauses, "fee_manager" runs the fee
+// schedule and proposes fee changes, "treasurer" withdraws accrued
+// platform fees.
+// The role admin (always the owner, see OWNERSHIP) grants and revokes
+// them, and can hand a role's management to another role with
+// set_role_admin.
+// Nobody holds a role until it is granted.
+
+#[default_impl]
+#[contractimpl]
+impl AccessControl for TippingContract {
+    // The role admin only moves through the ownership calls above
+    fn transfer_admin_role(e: &Env, _new_admin: Address, _live_until_ledger: u32) {
+        panic_with_error!(e, TippingError::Unauthorized);
+    }
+
+    fn accept_admin_transfer(e: &Env) {
+        panic_with_error!(e, TippingError::Unauthorized);
+    }
+
+    fn renounce_admin(e: &Env) {
+        panic_with_error!(e, TippingError::Unauthorized);
+    }
+}
+
+impl TippingContract {
+    /// Extend the contract instance (admin config, allowlist) per the TTL policy
+    fn extend_instance(env: &Env) {
+        let ttl = Self::get_ttl_config(env.clone());
+        env.storage().instance().extend_ttl(ttl.threshold, ttl.extend_to);
+    }
+
+    /// Store a due fee change as the platform fee; no pause check, so
+    /// propose_fee_change can settle one while paused
+    fn apply_fee_change(env: &Env) -> Result<(), TippingError> {
+        let change = Self::get_pending_fee_change(env.clone())
+            .ok_or(TippingError::FeeChangeNotFound)?;
+        if change.effective_ledger > env.ledger().sequence() {
+            return Err(TippingError::FeeChangeNotReady);
+        }
+
+        let old_fee_bps: u32 = env.storage()
+            .instance()
+            .get(&DataKey::PlatformFeeBps)
+            .unwrap_or(DEFAULT_PLATFORM_FEE_BPS);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformFeeBps, &change.new_fee_bps);
+        env.storage().instance().remove(&DataKey::PendingFeeChange);
+
+        FeeUpdatedEvent { old_fee_bps, new_fee_bps: change.new_fee_bps }.publish(env);
+
+        Ok(())
+    }
+
+    /// Apply the proposed fee change if its effective ledger has passed
+    fn settle_fee_change(env: &Env) -> Result<(), TippingError> {
+        if Self::get_pending_fee_change(env.clone())
+            .is_some_and(|change| change.effective_ledger <= env.ledger().sequence())
+        {
+            Self::apply_fee_change(env)?;
+        }
+        Ok(())
+    }
+
+    /// Platform fee at the current ledger, counting a due but unactivated change
+    fn base_fee_bps(env: &Env) -> u32 {
+        match Self::get_pending_fee_change(env.clone()) {
+            Some(change) if change.effective_ledger <= env.ledger().sequence() => change.new_fee_bps,
+            _ => env.storage()
+                .instance()
+                .get(&DataKey::PlatformFeeBps)
+                .unwrap_or(DEFAULT_PLATFORM_FEE_BPS),
+        }
+    }
+
+    /// Fee an author pays at the current ledger; see FEE SCHEDULE
+    fn fee_bps_for(env: &Env, author: &Address) -> u32 {
+        let base_fee_bps = Self::base_fee_bps(env);
+        let fee_bps = match Self::get_author_fee(env.clone(), author.clone()) {
+            Some(bps) => bps,
+            None => {
+                let volume = Self::native_volume(env, author);
+                Self::get_fee_tiers(env.clone())
+                    .iter()
+                    .filter(|tier| volume >= tier.min_volume)
+                    .last()
+                    .map_or(base_fee_bps, |tier| tier.fee_bps)
+            }
+        }
+        .min(base_fee_bps);
+
+        let ledger = env.ledger().sequence();
+        match Self::get_fee_holiday(env.clone()) {
+            Some(holiday) if (holiday.start_ledger..=holiday.end_ledger).contains(&ledger) => {
+                fee_bps.min(holiday.fee_bps)
+            }
+            _ => fee_bps,
+        }
+    }
+
+    /// Lifetime gross an author was tipped in the native token, which volume tiers count
+    fn native_volume(env: &Env, author: &Address) -> i128 {
+        let Ok(native_token) = Self::get_native_token(env.clone()) else {
+            return 0;
+        };
+        Self::get_author_stats(env.clone(), author.clone())
+            .tokens
+            .iter()
+            .find(|entry| entry.token == native_token)
+            .map_or(0, |entry| entry.gross)
+    }
+
+    /// Extend a persistent entry per the TTL policy, if it exists
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        if env.storage().persistent().has(key) {
+            let ttl = Self::get_ttl_config(env.clone());
+            env.storage().persistent().extend_ttl(key, ttl.threshold, ttl.extend_to);
+        }
+    }
+
+    /// Check a tip against its token's limits and count it toward them
+    fn enforce_tip_limits(
+        env: &Env,
+        tipper: &Address,
+        article_id: &Symbol,
+        token: &Address,
+        amount: i128,
+    ) -> Result<(), TippingError> {
+        let Some(limits) = Self::get_tip_limits(env.clone(), token.clone()) else {
+            return Ok(());
+        };
+        if limits.max_tip > 0 && amount > limits.max_tip {
+            return Err(TippingError::TipLimitExceeded);
+        }
+
+        let now = env.ledger().sequence();
+        if limits.max_tips_per_window > 0 {
+            let window = now / limits.window_ledgers;
+            let key = DataKey::TipWindow(tipper.clone(), article_id.clone(), token.clone());
+            let count = match env.storage().temporary().get::<_, TipWindowUsage>(&key) {
+                Some(usage) if usage.window == window => usage.count,
+                _ => 0,
+            };
+            if count >= limits.max_tips_per_window {
+                return Err(TippingError::TipLimitExceeded);
+            }
+            env.storage().temporary().set(&key, &TipWindowUsage { window, count: count + 1 });
+            let remaining = (window + 1) * limits.window_ledgers - now;
+            env.storage().temporary().extend_ttl(&key, remaining, remaining);
+        }
+
+        if limits.daily_cap > 0 {
+            let mut recent = Self::recent_spend(env, tipper, token);
+            let spent: i128 = recent.iter().map(|spend| spend.amount).sum();
+            if spent + amount > limits.daily_cap {
+                return Err(TippingError::TipLimitExceeded);
+            }
+
+            let bucket_start = now - now % SPEND_BUCKET_LEDGERS;
+            match recent.last() {
+                Some(last) if last.bucket_start == bucket_start => {
+                    recent.set(recent.len() - 1, TipSpend { bucket_start, amount: last.amount + amount });
+                }
+                _ => recent.push_back(TipSpend { bucket_start, amount }),
+            }
+            let key = DataKey::TipSpend(tipper.clone(), token.clone());
+            env.storage().temporary().set(&key, &recent);
+            env.storage().temporary().extend_ttl(&key, DAY_IN_LEDGERS, DAY_IN_LEDGERS);
+        }
+
+        Ok(())
+    }
+
+    /// A tipper's spend buckets that still count toward the daily cap
+    fn recent_spend(env: &Env, tipper: &Address, token: &Address) -> Vec<TipSpend> {
+        let since = env.ledger().sequence().saturating_sub(DAY_IN_LEDGERS);
+        let mut recent = vec![env];
+        let stored: Vec<TipSpend> = env.storage()
+            .temporary()
+            .get(&DataKey::TipSpend(tipper.clone(), token.clone()))
+            .unwrap_or(vec![env]);
+        for spend in stored.iter() {
+            if spend.bucket_start + SPEND_BUCKET_LEDGERS > since {
+                recent.push_back(spend);
+            }
+        }
+        recent
+    }
+
+    /// Add a platform fee the contract now holds to the token's accrued total
+    fn accrue_fee(env: &Env, token: &Address, fee: i128) {
+        let key = DataKey::AccruedFees(token.clone());
+        let accrued = Self::get_accrued_fees(env.clone(), token.clone());
+        env.storage().persistent().set(&key, &(accrued + fee));
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Message carried by a batch item; text and hash are mutually exclusive
+    fn batch_item_message(item: &TipItem) -> Result<Option<TipMessage>, TippingError> {
+        match (&item.message, &item.message_hash) {
+            (Some(_), Some(_)) => Err(TippingError::InvalidMessage),
+            (Some(text), None) => Ok(Some(TipMessage::Text(text.clone()))),
+            (None, Some(hash)) => Ok(Some(TipMessage::Hash(hash.clone()))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Validate a tip, then pay it to the article's author, or hold it in
+    /// escrow if the article has no registered author yet. Caller handles auth.
+    fn execute_tip(
+        env: &Env,
+        tipper: &Address,
+        article_id: Symbol,
+        highlight_id: Option<String>,
+        token: Address,
+        amount: i128,
+        message: Option<TipMessage>,
+    ) -> Result<TipReceipt, TippingError> {
+        // Validate token and minimum amount
+        let minimum_tip = Self::get_min_tip(env.clone(), token.clone())?;
+        if amount < minimum_tip {
+            return Err(TippingError::BelowMinimumTip);
+        }
+        Self::enforce_tip_limits(env, tipper, &article_id, &token, amount)?;
+
+        let author = Self::get_article_author(env.clone(), article_id.clone());
+        if let Some(TipMessage::Text(text)) = &message {
+            if text.is_empty() || text.len() > MAX_MESSAGE_LEN {
+                return Err(TippingError::InvalidMessage);
+            }
+            if let Some(author) = &author {
+                if !Self::accepts_public_messages(env.clone(), author.clone()) {
+                    return Err(TippingError::MessagesDisabled);
+                }
+            }
+        }
+
+        let tip_id = Self::next_tip_id(env);
+
+        // Stored up front so it survives escrow and the refund window
+        if let Some(message) = message {
+            env.storage().persistent().set(&DataKey::TipMessage(tip_id), &message);
+            Self::extend_persistent(env, &DataKey::TipMessage(tip_id));
+        }
+
+        let tip = TipRequest {
+            tip_id,
+            tipper: tipper.clone(),
+            article_id: article_id.clone(),
+            highlight_id,
+            token,
+            amount,
+        };
+
+        // Resolve recipient from the article registry
+        match author {
+            Some(_) if Self::get_pending_window(env.clone()) > 0 => Self::hold_pending_tip(env, tip),
+            Some(author) => Self::settle_tip(env, tipper, &author, tip),
+            None => Self::escrow_tip(env, tip),
+        }
+    }
+
+    /// Pay a tip from `payer` to the author and record it everywhere
+    /// Highlight tips also land in the highlight's history
+    fn settle_tip(env: &Env, payer: &Address, author: &Address, tip: TipRequest) -> Result<TipReceipt, TippingError> {
+        let TipRequest { tip_id: new_tip_id, tipper, article_id, highlight_id, token, amount } = tip;
+
+        if !env.storage().instance().has(&DataKey::PlatformAddress) {
+            return Err(TippingError::NotInitialized);
+        }
+
+        // Calculate fees; co-authored articles use the registered author's schedule
+        Self::settle_fee_change(env)?;
+        let platform_fee_bps = Self::fee_bps_for(env, author);
+        let platform_fee = (amount * platform_fee_bps as i128) / 10_000;
+        let author_share = amount - platform_fee;
+
+        // Get token client for the tipped asset
+        let token_client = token::TokenClient::new(env, &token);
+
+        // Transfer each co-author's part of the author share
+        let splits = Self::get_article_splits(env.clone(), article_id.clone());
+        let net_parts = split_amount(env, author_share, &splits);
+        let fee_parts = split_amount(env, platform_fee, &splits);
+        let mut payouts = vec![env];
+        for (i, share) in splits.iter().enumerate() {
+            let net = net_parts.get_unchecked(i as u32);
+            if net > 0 {
+                token_client.transfer(payer, &share.recipient, &net);
+            }
+            payouts.push_back(RecipientPayout { recipient: share.recipient, amount: net });
+        }
+
+        // Keep the platform fee in the treasury; held tips need no transfer
+        if platform_fee > 0 {
+            let contract = env.current_contract_address();
+            if *payer != contract {
+                token_client.transfer(payer, &contract, &platform_fee);
+            }
+            Self::accrue_fee(env, &token, platform_fee);
+        }
+
+        // Track cumulative tips per token for statistics (not balances)
+        // Highlight tips count toward the parent article's total
+        Self::fold_legacy_article_total(env, &article_id)?;
+        let current_total: i128 = env.storage()
+            .persistent()
+            .get(&DataKey::ArticleTotalTips(article_id.clone(), token.clone()))
+            .unwrap_or(0);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArticleTotalTips(article_id.clone(), token.clone()), &(current_total + amount));
+        Self::add_article_token(env, &article_id, &token);
+
+        let mut touched = vec![
+            env,
+            DataKey::ArticleAuthor(article_id.clone()),
+            DataKey::ArticleTotalTips(article_id.clone(), token.clone()),
+            DataKey::ArticleTokens(article_id.clone()),
+            DataKey::TotalVolume(token.clone()),
+            DataKey::ArticleSplits(article_id.clone()),
+        ];
+
+        match &highlight_id {
+            None => {
+                // Reserve indices for any legacy history so this tip lands after it
+                Self::migrate_legacy_article_tips(env, &article_id, 0)?;
+
+                let tip = SimpleTip {
+                    tipper: tipper.clone(),
+                    token: token.clone(),
+                    amount,
+                    timestamp: env.ledger().timestamp(),
+                };
+
+                // Append tip to the article's history
+                let tip_index: u32 = env.storage()
+                    .persistent()
+                    .get(&DataKey::ArticleTipCount(article_id.clone()))
+                    .unwrap_or(0);
+
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArticleTip(article_id.clone(), tip_index), &tip);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArticleTipCount(article_id.clone()), &(tip_index + 1));
+
+                touched.push_back(DataKey::ArticleTipCount(article_id.clone()));
+                touched.push_back(DataKey::ArticleTip(article_id.clone(), tip_index));
+            }
+            Some(highlight_id) => {
+                let highlight_total: i128 = env.storage()
+                    .persistent()
+                    .get(&DataKey::ArticleHighlightTotal(article_id.clone(), token.clone()))
+                    .unwrap_or(0);
+
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArticleHighlightTotal(article_id.clone(), token.clone()), &(highlight_total + amount));
+
+                // Reserve indices for any legacy history so this tip lands after it
+                Self::migrate_legacy_highlight_tips(env, highlight_id, 0)?;
+
+                let tip = HighlightTip {
+                    highlight_id: highlight_id.clone(),
+                    article_id: article_id.clone(),
+                    tipper: tipper.clone(),
+                    token: token.clone(),
+                    amount,
+                    timestamp: env.ledger().timestamp(),
+                };
+
+                // Append tip to the highlight's history
+                let tip_index: u32 = env.storage()
+                    .persistent()
+                    .get(&DataKey::HighlightTipCount(highlight_id.clone()))
+                    .unwrap_or(0);
+
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::HighlightTip(highlight_id.clone(), tip_index), &tip);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::HighlightTipCount(highlight_id.clone()), &(tip_index + 1));
+
+                touched.push_back(DataKey::ArticleHighlightTotal(article_id.clone(), token.clone()));
+                touched.push_back(DataKey::HighlightTipCount(highlight_id.clone()));
+                touched.push_back(DataKey::HighlightTip(highlight_id.clone(), tip_index));
+            }
+        }
+
+        // Update total volume for this token
+        let total_volume: i128 = env.storage()
+            .persistent()
+            .get(&DataKey::TotalVolume(token.clone()))
+            .unwrap_or(0);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalVolume(token.clone()), &(total_volume + amount));
+
+        for (i, payout) in payouts.iter().enumerate() {
+            let fee = fee_parts.get_unchecked(i as u32);
+            Self::record_author_tip(env, &payout.recipient, &token, payout.amount + fee, fee);
+            touched.push_back(DataKey::AuthorStats(payout.recipient));
+        }
+        Self::record_tip(env, &TipRecord {
+            tip_id: new_tip_id,
+            article_id: Some(article_id.clone()),
+            highlight_id: highlight_id.clone(),
+            subscription_id: None,
+            tipper: tipper.clone(),
+            author: author.clone(),
+            token: token.clone(),
+            amount,
+            platform_fee,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        // Keep the entries this tip touched alive
+        Self::extend_instance(env);
+        for key in touched {
+            Self::extend_persistent(env, &key);
+        }
+
+        TipEvent {
+            article_id,
+            tip_id: new_tip_id,
+            highlight_id,
+            token: token.clone(),
+            tipper: tipper.clone(),
+            recipient: author.clone(),
+            gross: amount,
+            fee: platform_fee,
+            net: author_share,
+            payouts: payouts.clone(),
+            message: Self::get_tip_message(env.clone(), new_tip_id),
+        }
+        .publish(env);
+
+        // Create receipt
+        Ok(TipReceipt {
+            tip_id: new_tip_id,
+            token,
+            amount_sent: amount,
+            author_received: author_share,
+            platform_fee,
+            timestamp: env.ledger().timestamp(),
+            payouts,
+        })
+    }
+
+    /// Hold a tip in the contract for the refund window
+    fn hold_pending_tip(env: &Env, tip: TipRequest) -> Result<TipReceipt, TippingError> {
+        token::TokenClient::new(env, &tip.token)
+            .transfer(&tip.tipper, env.current_contract_address(), &tip.amount);
+
+        let release_ledger = env.ledger().sequence() + Self::get_pending_window(env.clone());
+        let pending = PendingTip {
+            tip_id: tip.tip_id,
+            tipper: tip.tipper.clone(),
+            article_id: tip.article_id.clone(),
+            highlight_id: tip.highlight_id.clone(),
+            token: tip.token.clone(),
+            amount: tip.amount,
+            release_ledger,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingTip(tip.tip_id), &pending);
+        Self::extend_instance(env);
+        Self::extend_persistent(env, &DataKey::PendingTip(tip.tip_id));
+
+        TipPendingEvent {
+            article_id: tip.article_id,
+            tip_id: tip.tip_id,
+            highlight_id: tip.highlight_id,
+            token: tip.token.clone(),
+            tipper: tip.tipper,
+            amount: tip.amount,
+            release_ledger,
+        }
+        .publish(env);
+
+        Ok(TipReceipt {
+            tip_id: tip.tip_id,
+            token: tip.token,
+            amount_sent: tip.amount,
+            author_received: 0,
+            platform_fee: 0,
+            timestamp: env.ledger().timestamp(),
+            payouts: vec![env],
+        })
+    }
+
+    /// Send a pending tip back to its tipper while its window is still open
+    fn refund_pending_tip(env: &Env, pending: PendingTip, reversed: bool) -> Result<(), TippingError> {
+        if env.ledger().sequence() >= pending.release_ledger {
+            return Err(TippingError::PendingWindowClosed);
+        }
+
+        env.storage().persistent().remove(&DataKey::PendingTip(pending.tip_id));
+        token::TokenClient::new(env, &pending.token)
+            .transfer(&env.current_contract_address(), &pending.tipper, &pending.amount);
+
+        PendingTipRefundedEvent {
+            tip_id: pending.tip_id,
+            tipper: pending.tipper,
+            token: pending.token,
+            amount: pending.amount,
+            reversed,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Hold a tip in the contract until the article's author claims it
+    fn escrow_tip(env: &Env, tip: TipRequest) -> Result<TipReceipt, TippingError> {
+        token::TokenClient::new(env, &tip.token)
+            .transfer(&tip.tipper, env.current_contract_address(), &tip.amount);
+
+        let index: u32 = env.storage()
+            .persistent()
+            .get(&DataKey::EscrowCount(tip.article_id.clone()))
+            .unwrap_or(0);
+        let escrowed = EscrowedTip {
+            index,
+            tip_id: tip.tip_id,
+            tipper: tip.tipper.clone(),
+            highlight_id: tip.highlight_id.clone(),
+            token: tip.token.clone(),
+            amount: tip.amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowTip(tip.article_id.clone(), index), &escrowed);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowCount(tip.article_id.clone()), &(index + 1));
+
+        let total_key = DataKey::EscrowTotal(tip.article_id.clone(), tip.token.clone());
+        let held: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage().persistent().set(&total_key, &(held + tip.amount));
+
+        Self::extend_instance(env);
+        for key in [
+            DataKey::EscrowTip(tip.article_id.clone(), index),
+            DataKey::EscrowCount(tip.article_id.clone()),
+            total_key,
+        ] {
+            Self::extend_persistent(env, &key);
+        }
+
+        TipEscrowedEvent {
+            article_id: tip.article_id,
+            tip_id: tip.tip_id,
+            index,
+            highlight_id: tip.highlight_id,
+            token: tip.token.clone(),
+            tipper: tip.tipper,
+            amount: tip.amount,
+        }
+        .publish(env);
+
+        Ok(TipReceipt {
+            tip_id: tip.tip_id,
+            token: tip.token,
+            amount_sent: tip.amount,
+            author_received: 0,
+            platform_fee: 0,
+            timestamp: env.ledger().timestamp(),
+            payouts: vec![env],
+        })
+    }
+
+    /// Remove an escrowed tip and release it from the article's held total
+    fn take_escrowed_tip(env: &Env, article_id: &Symbol, index: u32) -> Option<EscrowedTip> {
+        let key = DataKey::EscrowTip(article_id.clone(), index);
+        let escrowed: EscrowedTip = env.storage().persistent().get(&key)?;
+        env.storage().persistent().remove(&key);
+
+        let total_key = DataKey::EscrowTotal(article_id.clone(), escrowed.token.clone());
+        let held: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage().persistent().set(&total_key, &(held - escrowed.amount));
+
+        Some(escrowed)
+    }
+
+    /// Move a subscription between Active, Paused and Cancelled on its subscriber's behalf
+    fn set_subscription_status(env: &Env, subscription_id: u64, status: SubscriptionStatus) -> Result<(), TippingError> {
+        let mut subscription = Self::get_subscription(env.clone(), subscription_id)
+            .ok_or(TippingError::SubscriptionNotFound)?;
+        subscription.subscriber.require_auth();
+
+        let allowed = match status {
+            SubscriptionStatus::Paused => subscription.status == SubscriptionStatus::Active,
+            SubscriptionStatus::Active => subscription.status == SubscriptionStatus::Paused,
+            _ => matches!(subscription.status, SubscriptionStatus::Active | SubscriptionStatus::Paused),
+        };
+        if !allowed {
+            return Err(TippingError::SubscriptionInactive);
+        }
+
+        if status == SubscriptionStatus::Active {
+            subscription.next_charge_at = subscription.next_charge_at.max(env.ledger().timestamp());
+        }
+        subscription.status = status;
+        Self::save_subscription(env, &subscription);
+
+        SubscriptionStatusEvent { subscription_id, status }.publish(env);
+
+        Ok(())
+    }
+
+    fn save_subscription(env: &Env, subscription: &Subscription) {
+        let key = DataKey::Subscription(subscription.id);
+        env.storage().persistent().set(&key, subscription);
+        Self::extend_instance(env);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Add a tip to its recipient's earnings aggregates
+    fn record_author_tip(env: &Env, author: &Address, token: &Address, gross: i128, fee: i128) {
+        let now = env.ledger().timestamp();
+        let mut stats = Self::get_author_stats(env.clone(), author.clone());
+        if stats.tip_count == 0 {
+            stats.first_tip_at = now;
+        }
+        stats.tip_count += 1;
+        stats.last_tip_at = now;
+
+        let position = stats.tokens.iter().position(|entry| entry.token == *token);
+        let mut entry = match position {
+            Some(i) => stats.tokens.get_unchecked(i as u32),
+            None => AuthorTokenStats {
+                token: token.clone(),
+                tip_count: 0,
+                gross: 0,
+                net: 0,
+                fees: 0,
+            },
+        };
+        entry.tip_count += 1;
+        entry.gross += gross;
+        entry.net += gross - fee;
+        entry.fees += fee;
+        match position {
+            Some(i) => stats.tokens.set(i as u32, entry),
+            None => stats.tokens.push_back(entry),
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::AuthorStats(author.clone()), &stats);
+    }
+
+    /// Store a tip under its id and index it for its tipper and their author
+    fn record_tip(env: &Env, tip: &TipRecord) {
+        env.storage().persistent().set(&DataKey::Tip(tip.tip_id), tip);
+
+        let mut stats = Self::get_tipper_stats(env.clone(), tip.tipper.clone());
+        let tip_index = stats.tip_count;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TipperTip(tip.tipper.clone(), tip_index), &tip.tip_id);
+        stats.tip_count += 1;
+        add_to_token_total(&mut stats.given, &tip.token, tip.amount);
+
+        let given_key = DataKey::SupporterGiven(tip.author.clone(), tip.tipper.clone());
+        let mut given: Vec<TokenTotal> = match env.storage().persistent().get(&given_key) {
+            Some(given) => given,
+            None => {
+                // First tip from this wallet to this author
+                stats.authors_supported += 1;
+                let supporter_index = Self::get_supporter_count(env.clone(), tip.author.clone());
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Supporter(tip.author.clone(), supporter_index), &tip.tipper);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::SupporterCount(tip.author.clone()), &(supporter_index + 1));
+                Self::extend_persistent(env, &DataKey::Supporter(tip.author.clone(), supporter_index));
+                vec![env]
+            }
+        };
+        let given_total = add_to_token_total(&mut given, &tip.token, tip.amount);
+        Self::rank_supporter(env, &tip.author, &tip.tipper, &tip.token, given_total);
+
+        env.storage().persistent().set(&given_key, &given);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TipperStats(tip.tipper.clone()), &stats);
+
+        for key in [
+            DataKey::Tip(tip.tip_id),
+            DataKey::TipperTip(tip.tipper.clone(), tip_index),
+            DataKey::TipperStats(tip.tipper.clone()),
+            DataKey::SupporterCount(tip.author.clone()),
+            given_key,
+        ] {
+            Self::extend_persistent(env, &key);
+        }
+    }
+
+    /// Issue the next tip id
+    fn next_tip_id(env: &Env) -> u64 {
+        let tip_id = env.storage()
+            .persistent()
+            .get::<_, u64>(&DataKey::TipCounter)
+            .unwrap_or(0) + 1;
+        env.storage().persistent().set(&DataKey::TipCounter, &tip_id);
+        Self::extend_persistent(env, &DataKey::TipCounter);
+        tip_id
+    }
+
+    /// Move a tipper to their place in the author's top supporters for `token`
+    fn rank_supporter(env: &Env, author: &Address, tipper: &Address, token: &Address, given_total: i128) {
+        let key = DataKey::TopSupporters(author.clone(), token.clone());
+        let mut top: Vec<RankedSupporter> = env.storage().persistent().get(&key).unwrap_or(vec![env]);
+        if let Some(i) = top.iter().position(|entry| entry.tipper == *tipper) {
+            top.remove(i as u32);
+        }
+
+        // Totals only grow, so a listed tipper never moves down
+        let rank = top.iter().position(|entry| entry.amount < given_total).unwrap_or(top.len() as usize) as u32;
+        if rank >= MAX_TOP_SUPPORTERS {
+            return;
+        }
+        top.insert(rank, RankedSupporter { tipper: tipper.clone(), amount: given_total });
+        if top.len() > MAX_TOP_SUPPORTERS {
+            top.pop_back();
+        }
+        env.storage().persistent().set(&key, &top);
+        Self::extend_persistent(env, &key);
+    }
+
+    fn tipper_tips_page(env: &Env, tipper: &Address, start: u32, limit: u32, newest_first: bool) -> Vec<TipRecord> {
+        let count = Self::get_tipper_stats(env.clone(), tipper.clone()).tip_count;
+        let mut tips = vec![env];
+        for index in page_indices(env, count, start, limit, newest_first) {
+            if let Some(tip_id) = env.storage()
+                .persistent()
+                .get::<DataKey, u64>(&DataKey::TipperTip(tipper.clone(), index)) {
+                if let Some(tip) = Self::get_tip(env.clone(), tip_id) {
+                    tips.push_back(tip);
+                }
+            }
+        }
+        tips
+    }
+
+    /// Schema 0 → 1: the admin becomes the OZ owner and role admin, the
+    /// native token and its allowlist entry are stored rather than hard-coded,
+    /// and total volume is keyed by token
+    fn migrate_from_v0(env: &Env) -> Result<(), TippingError> {
+        // The legacy Admin key encodes the same as the access-control admin
+        // slot, so the old admin already holds that role; only ownership moves.
+        if let Some(admin) = env.storage().instance().get::<_, Address>(&LegacyDataKey::Admin) {
+            if ownable::get_owner(env).is_none() {
+                ownable::set_owner(env, &admin);
+            }
+        }
+
+        if !env.storage().instance().has(&DataKey::NativeToken) {
+            let native_token = Address::from_string(&String::from_str(env, LEGACY_NATIVE_TOKEN));
+            env.storage().instance().set(&DataKey::NativeToken, &native_token);
+            env.storage().instance().set(&DataKey::AllowedTokens, &vec![env, native_token.clone()]);
+            env.storage().instance().set(&DataKey::TokenMinimum(native_token), &MINIMUM_TIP_STROOPS);
+        }
+
+        if let Some(volume) = env.storage().persistent().get::<_, i128>(&LegacyDataKey::TotalVolume) {
+            let native_token = Self::get_native_token(env.clone())?;
+            let key = DataKey::TotalVolume(native_token.clone());
+            let total = Self::get_total_volume(env.clone(), native_token) + volume;
+            env.storage().persistent().set(&key, &total);
+            env.storage().persistent().remove(&LegacyDataKey::TotalVolume);
+            Self::extend_persistent(env, &key);
+        }
+
+        Ok(())
+    }
+
+    /// Move up to `max` tips of a legacy article tip vector into indexed
+    /// entries; returns how many are left. The first call, even with `max`
+    /// 0, reserves indices 0..len for the vector so later tips append after
+    /// it. Legacy tips were always paid in the native token.
+    fn migrate_legacy_article_tips(env: &Env, article_id: &Symbol, max: u32) -> Result<u32, TippingError> {
+        // The legacy total is folded in even when there is no history to move
+        Self::fold_legacy_article_total(env, article_id)?;
+
+        let legacy_key = LegacyDataKey::ArticleTips(article_id.clone());
+        let Some(legacy_tips) = env.storage().persistent().get::<_, Vec<LegacySimpleTip>>(&legacy_key) else {
+            return Ok(0);
+        };
+
+        let native_token = Self::get_native_token(env.clone())?;
+        let cursor_key = DataKey::LegacyTipCursor(article_id.clone());
+        let cursor: u32 = match env.storage().persistent().get(&cursor_key) {
+            Some(cursor) => cursor,
+            None => {
+                // A legacy vector never coexists with indexed tips, so its
+                // length is the article's whole history so far
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArticleTipCount(article_id.clone()), &legacy_tips.len());
+                0
+            }
+        };
+
+        let end = cursor.saturating_add(max).min(legacy_tips.len());
+        for index in cursor..end {
+            let legacy = legacy_tips.get_unchecked(index);
+            let tip = SimpleTip {
+                tipper: legacy.tipper,
+                token: native_token.clone(),
+                amount: legacy.amount,
+                timestamp: legacy.timestamp,
+            };
+            let key = DataKey::ArticleTip(article_id.clone(), index);
+            env.storage().persistent().set(&key, &tip);
+            Self::extend_persistent(env, &key);
+        }
+
+        if end == legacy_tips.len() {
+            env.storage().persistent().remove(&legacy_key);
+            env.storage().persistent().remove(&cursor_key);
+        } else {
+            env.storage().persistent().set(&cursor_key, &end);
+            Self::extend_persistent(env, &cursor_key);
+            let ttl = Self::get_ttl_config(env.clone());
+            env.storage().persistent().extend_ttl(&legacy_key, ttl.threshold, ttl.extend_to);
+        }
+
+        Ok(legacy_tips.len() - end)
+    }
+
+    /// Move an article's legacy native-token total into its per-token total
+    fn fold_legacy_article_total(env: &Env, article_id: &Symbol) -> Result<(), TippingError> {
+        let total_key = LegacyDataKey::ArticleTotalTips(article_id.clone());
+        let Some(legacy_total) = env.storage().persistent().get::<_, i128>(&total_key) else {
+            return Ok(());
+        };
+
+        let native_token = Self::get_native_token(env.clone())?;
+        let key = DataKey::ArticleTotalTips(article_id.clone(), native_token.clone());
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(total + legacy_total));
+        env.storage().persistent().remove(&total_key);
+        Self::add_article_token(env, article_id, &native_token);
+        Self::extend_persistent(env, &key);
+        Self::extend_persistent(env, &DataKey::ArticleTokens(article_id.clone()));
+
+        Ok(())
+    }
+
+    /// Get the tokens an article holds a total in
+    fn article_tokens(env: &Env, article_id: &Symbol) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArticleTokens(article_id.clone()))
+            .unwrap_or(vec![env])
+    }
+
+    /// Scan the next tips of an article's history within `budget`, then
+    /// rewrite its totals if the scan is done. Returns whether it is.
+    fn recompute_article_total(env: &Env, article_id: &Symbol, budget: &mut u32) -> Result<bool, TippingError> {
+        // Reserve indices for any legacy history so the scan covers it
+        Self::migrate_legacy_article_tips(env, article_id, 0)?;
+
+        let progress_key = DataKey::TotalRecompute(article_id.clone());
+        let mut progress: TotalRecompute = match env.storage().persistent().get(&progress_key) {
+            Some(progress) => progress,
+            None => {
+                // Every token holding a total is included, allowed or not,
+                // so one with no history left is zeroed
+                let mut sums = Map::new(env);
+                for token in Self::article_tokens(env, article_id).iter() {
+                    sums.set(token, 0);
+                }
+                TotalRecompute { next_index: 0, sums }
+            }
+        };
+
+        let count = Self::get_article_tip_count(env.clone(), article_id.clone());
+        let indices = page_indices(env, count, progress.next_index, *budget, false);
+        progress.next_index += indices.len();
+        *budget -= indices.len();
+        for tip in Self::load_article_tips(env, article_id, indices).iter() {
+            let sum = progress.sums.get(tip.token.clone()).unwrap_or(0);
+            progress.sums.set(tip.token, sum + tip.amount);
+        }
+
+        // Tips made meanwhile are appended, so they are scanned too
+        if progress.next_index < count {
+            env.storage().persistent().set(&progress_key, &progress);
+            Self::extend_persistent(env, &progress_key);
+            return Ok(false);
+        }
+
+        for (token, direct_total) in progress.sums.iter() {
+            let old_total = Self::get_article_total_tips(env.clone(), article_id.clone(), token.clone());
+            let new_total = direct_total
+                + Self::get_article_highlight_total(env.clone(), article_id.clone(), token.clone());
+            if old_total == new_total {
+                continue;
+            }
+
+            let key = DataKey::ArticleTotalTips(article_id.clone(), token.clone());
+            env.storage().persistent().set(&key, &new_total);
+            Self::add_article_token(env, article_id, &token);
+            Self::extend_persistent(env, &key);
+            Self::extend_persistent(env, &DataKey::ArticleTokens(article_id.clone()));
+
+            ArticleTotalCorrectedEvent {
+                article_id: article_id.clone(),
+                token,
+                old_total,
+                new_total,
+            }
+            .publish(env);
+        }
+        env.storage().persistent().remove(&progress_key);
+
+        Ok(true)
+    }
+
+    /// Record that an article holds a total in `token`
+    fn add_article_token(env: &Env, article_id: &Symbol, token: &Address) {
+        let mut tokens = Self::article_tokens(env, article_id);
+        if !tokens.contains(token) {
+            tokens.push_back(token.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::ArticleTokens(article_id.clone()), &tokens);
+        }
+    }
+
+    /// Move up to `max` tips of a legacy highlight tip vector into indexed
+    /// entries (see `migrate_legacy_article_tips`)
+    fn migrate_legacy_highlight_tips(env: &Env, highlight_id: &String, max: u32) -> Result<u32, TippingError> {
+        let legacy_key = LegacyDataKey::HighlightTips(highlight_id.clone());
+        let Some(legacy_tips) = env.storage().persistent().get::<_, Vec<LegacyHighlightTip>>(&legacy_key) else {
+            return Ok(0);
+        };
+
+        let native_token = Self::get_native_token(env.clone())?;
+        let cursor_key = DataKey::LegacyHighlightTipCursor(highlight_id.clone());
+        let cursor: u32 = match env.storage().persistent().get(&cursor_key) {
+            Some(cursor) => cursor,
+            None => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::HighlightTipCount(highlight_id.clone()), &legacy_tips.len());
+                0
+            }
+        };
+
+        let end = cursor.saturating_add(max).min(legacy_tips.len());
+        for index in cursor..end {
+            let legacy = legacy_tips.get_unchecked(index);
+            let tip = HighlightTip {
+                highlight_id: legacy.highlight_id,
+                article_id: legacy.article_id,
+                tipper: legacy.tipper,
+                token: native_token.clone(),
+                amount: legacy.amount,
+                timestamp: legacy.timestamp,
+            };
+            let key = DataKey::HighlightTip(highlight_id.clone(), index);
+            env.storage().persistent().set(&key, &tip);
+            Self::extend_persistent(env, &key);
+        }
+
+        if end == legacy_tips.len() {
+            env.storage().persistent().remove(&legacy_key);
+            env.storage().persistent().remove(&cursor_key);
+        } else {
+            env.storage().persistent().set(&cursor_key, &end);
+            Self::extend_persistent(env, &cursor_key);
+            let ttl = Self::get_ttl_config(env.clone());
+            env.storage().persistent().extend_ttl(&legacy_key, ttl.threshold, ttl.extend_to);
+        }
+
+        Ok(legacy_tips.len() - end)
+    }
+
+    /// Article tips at `indices`, reading the legacy vector for any not migrated yet
+    fn load_article_tips(env: &Env, article_id: &Symbol, indices: Vec<u32>) -> Vec<SimpleTip> {
+        let mut tips = vec![env];
+        let mut legacy_tips: Option<Vec<LegacySimpleTip>> = None;
+        for index in indices.iter() {
+            if let Some(tip) = env.storage().persistent().get(&DataKey::ArticleTip(article_id.clone(), index)) {
+                tips.push_back(tip);
+                continue;
+            }
+            if legacy_tips.is_none() {
+                legacy_tips = env.storage().persistent().get(&LegacyDataKey::ArticleTips(article_id.clone()));
+            }
+            let (Some(legacy), Ok(native_token)) = (
+                legacy_tips.as_ref().and_then(|legacy_tips| legacy_tips.get(index)),
+                Self::get_native_token(env.clone()),
+            ) else {
+                continue;
+            };
+            tips.push_back(SimpleTip {
+                tipper: legacy.tipper,
+                token: native_token,
+                amount: legacy.amount,
+                timestamp: legacy.timestamp,
+            });
+        }
+        tips
+    }
+
+    /// Highlight tips at `indices`, reading the legacy vector for any not migrated yet
+    fn load_highlight_tips(env: &Env, highlight_id: &String, indices: Vec<u32>) -> Vec<HighlightTip> {
+        let mut tips = vec![env];
+        let mut legacy_tips: Option<Vec<LegacyHighlightTip>> = None;
+        for index in indices.iter() {
+            if let Some(tip) = env.storage().persistent().get(&DataKey::HighlightTip(highlight_id.clone(), index)) {
+                tips.push_back(tip);
+                continue;
+            }
+            if legacy_tips.is_none() {
+                legacy_tips = env.storage().persistent().get(&LegacyDataKey::HighlightTips(highlight_id.clone()));
+            }
+            let (Some(legacy), Ok(native_token)) = (
+                legacy_tips.as_ref().and_then(|legacy_tips| legacy_tips.get(index)),
+                Self::get_native_token(env.clone()),
+            ) else {
+                continue;
+            };
+            tips.push_back(HighlightTip {
+                highlight_id: legacy.highlight_id,
+                article_id: legacy.article_id,
+                tipper: legacy.tipper,
+                token: native_token,
+                amount: legacy.amount,
+                timestamp: legacy.timestamp,
+            });
+        }
+        tips
+    }
+}
+
+/// A validated tip on its way to being paid or escrowed
+struct TipRequest {
+    tip_id: u64,
+    tipper: Address,
+    article_id: Symbol,
+    highlight_id: Option<String>,
+    token: Address,
+    amount: i128,
+}
+
+/// Storage indices for one page of a `count`-long history
+fn page_indices(env: &Env, count: u32, start: u32, limit: u32, newest_first: bool) -> Vec<u32> {
+    let mut indices = vec![env];
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+    for position in start..end {
+        if newest_first {
+            indices.push_back(count - 1 - position);
+        } else {
+            indices.push_back(position);
+        }
+    }
+    indices
+}
+
+/// Split `total` by basis-point shares, rounding down; the first recipient takes the dust
+fn split_amount(env: &Env, total: i128, splits: &Vec<SplitShare>) -> Vec<i128> {
+    let mut parts = vec![env];
+    let mut allocated: i128 = 0;
+    for share in splits.iter() {
+        let part = total * share.share_bps as i128 / 10_000;
+        allocated += part;
+        parts.push_back(part);
+    }
+    if let Some(first) = parts.get(0) {
+        parts.set(0, first + total - allocated);
+    }
+    parts
+}
+
+/// Add `amount` to the entry for `token`, creating it if needed; returns the new total
+fn add_to_token_total(totals: &mut Vec<TokenTotal>, token: &Address, amount: i128) -> i128 {
+    match totals.iter().position(|total| total.token == *token) {
+        Some(i) => {
+            let mut total = totals.get_unchecked(i as u32);
+            total.amount += amount;
+            totals.set(i as u32, total.clone());
+            total.amount
+        }
+        None => {
+            totals.push_back(TokenTotal { token: token.clone(), amount });
+            amount
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use stellar_access::access_control::AccessControlError;
+    use stellar_access::ownable::OwnableError;
+    use stellar_contract_utils::pausable::{Paused, PausableError, Unpaused};
+    use soroban_sdk::{
+        contractclient, symbol_short,
+        testutils::{storage::{Instance as _, Persistent as _}, Address as _, Events as _, Ledger as _},
+        Env, Event as _, IntoVal, InvokeError, Val,
+    };
+
+    // The current code as built by `stellar contract build`; refresh it after changing the contract
+    const TIPPING_WASM: &[u8] = include_bytes!("../testdata/quilltip_tipping.wasm");
+
+    // The original release (schema 0), built from its source
+    const TIPPING_V0_WASM: &[u8] = include_bytes!("../testdata/quilltip_tipping_v0.wasm");
+
+    /// Entrypoints of the original release the upgrade tests drive
+    #[allow(dead_code)] // Only the generated client is used
+    #[contractclient(name = "TippingV0Client")]
+    pub trait TippingV0 {
+        fn initialize(env: Env, admin: Address, platform_address: Address, fee_bps: Option<u32>);
+        fn tip_article(env: Env, tipper: Address, article_id: Symbol, author: Address, amount: i128) -> Val;
+        fn tip_highlight_direct(
+            env: Env,
+            tipper: Address,
+            highlight_id: String,
+            article_id: Symbol,
+            author: Address,
+            amount: i128,
+        ) -> Val;
+        fn get_article_total_tips(env: Env, article_id: Symbol) -> i128;
+        fn get_total_volume(env: Env) -> i128;
+    }
+
+    /// Stand-in for the native token contract the original release hard-coded
+    #[contract]
+    pub struct MockNativeToken;
+
+    #[contractimpl]
+    impl MockNativeToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&to, &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            Self::mint(env.clone(), from, -amount);
+            Self::mint(env, to, amount);
+        }
+    }
+
+    /// Deploy the original release with the native token it pays in
+    fn create_v0_contract<'a>(env: &Env) -> (Address, TippingV0Client<'a>, MockNativeTokenClient<'a>) {
+        let native_token = Address::from_string(&String::from_str(env, LEGACY_NATIVE_TOKEN));
+        env.register_at(&native_token, MockNativeToken, ());
+        let contract_id = env.register(TIPPING_V0_WASM, ());
+        let client = TippingV0Client::new(env, &contract_id);
+        (contract_id, client, MockNativeTokenClient::new(env, &native_token))
+    }
+
+    /// Swap in the current code. The original release has no upgrade
+    /// entrypoint, so this runs the host call from inside the contract.
+    fn install_current_code(env: &Env, contract_id: &Address) -> BytesN<32> {
+        let wasm_hash = env.deployer().upload_contract_wasm(TIPPING_WASM);
+        env.as_contract(contract_id, || env.deployer().update_current_contract_wasm(wasm_hash.clone()));
+        wasm_hash
+    }
+
+    fn create_token<'a>(env: &Env, admin: &Address) -> (Address, token::StellarAssetClient<'a>) {
+        let sac = env.register_stellar_asset_contract_v2(admin.clone());
+        (sac.address(), token::StellarAssetClient::new(env, &sac.address()))
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        env.mock_all_auths();
+        
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        
+        let (xlm, _) = create_token(&env, &admin);
+        
+        assert_eq!(
+            client.try_initialize(&admin, &platform, &xlm, &Some(MAX_FEE_BPS + 1)),
+            Err(Ok(TippingError::FeeTooHigh))
+        );
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        
+        // Verify initialization: the native token is stored and allowed
+        assert_eq!(client.get_native_token(), xlm);
+        assert_eq!(client.get_allowed_tokens(), vec![&env, xlm.clone()]);
+        assert_eq!(client.get_min_tip(&xlm), MINIMUM_TIP_STROOPS);
+
+        let volume = client.get_total_volume(&xlm);
+        assert_eq!(volume, 0);
+    }
+    
+    #[test]
+    fn test_tip_article() {
+        let env = Env::default();
+        env.mock_all_auths();
+        
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+        
+        // Initialize contract
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.register_article(&author, &symbol_short!("article1"));
+        
+        // Send a tip
+        let receipt = client.tip_article(
+            &tipper,
+            &symbol_short!("article1"),
+            &token,
+            &1_000_000, // 0.1 XLM
+        );
+        
+        // Verify receipt
+        assert_eq!(receipt.token, token);
+        assert_eq!(receipt.amount_sent, 1_000_000);
+        assert_eq!(receipt.platform_fee, 25_000); // 2.5%
+        assert_eq!(receipt.author_received, 975_000); // 97.5%
+        
+        // Check author balance
+        let balance = client.get_balance(&author);
+        assert_eq!(balance, 975_000);
+        assert_eq!(client.get_balance(&platform), 0);
+        assert_eq!(client.get_balance(&contract_id), 25_000);
+        assert_eq!(client.get_accrued_fees(&token), 25_000);
+        
+        // Check tips for article
+        let tips = client.get_article_tips(&symbol_short!("article1"), &0, &10);
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips.get(0).unwrap().token, token);
+    }
+    
+    #[test]
+    fn test_minimum_tip_enforcement() {
+        let env = Env::default();
+        env.mock_all_auths();
+        
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, _) = create_token(&env, &admin);
+        
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.register_article(&author, &symbol_short!("article1"));
+        
+        // Try to send tip below minimum
+        let result = client.try_tip_article(
+            &tipper,
+            &symbol_short!("article1"),
+            &token,
+            &50_000, // Below minimum
+        );
+        assert_eq!(result, Err(Ok(TippingError::BelowMinimumTip)));
+    }
+    
+    #[test]
+    fn test_tip_with_immediate_transfers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+        
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+        
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.register_article(&author, &symbol_short!("art1"));
+        client.register_article(&author, &symbol_short!("art2"));
+        
+        // Send tips
+        client.tip_article(&tipper, &symbol_short!("art1"), &token, &1_000_000);
+        client.tip_article(&tipper, &symbol_short!("art2"), &token, &2_000_000);
+        
+        // Transfers go straight from tipper to author, the contract
+        // only holds the accrued platform fees
+        let token_client = token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&tipper), 7_000_000);
+        assert_eq!(token_client.balance(&contract_id), 75_000);
+        assert_eq!(client.get_accrued_fees(&token), 75_000);
+        
+        // Check article total tips (for NFT threshold tracking)
+        let art1_total = client.get_article_total_tips(&symbol_short!("art1"), &token);
+        assert_eq!(art1_total, 1_000_000);
+        
+        let art2_total = client.get_article_total_tips(&symbol_short!("art2"), &token);
+        assert_eq!(art2_total, 2_000_000);
+    }
+
+    #[test]
+    fn test_totals_tracked_per_token() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let (usdc, usdc_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &10_000_000);
+        usdc_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.add_allowed_token(&usdc, &50_000);
+
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        client.tip_article(&tipper, &article_id, &usdc, &50_000);
+
+        assert_eq!(client.get_article_total_tips(&article_id, &xlm), 1_000_000);
+        assert_eq!(client.get_article_total_tips(&article_id, &usdc), 50_000);
+        assert_eq!(client.get_total_volume(&xlm), 1_000_000);
+        assert_eq!(client.get_total_volume(&usdc), 50_000);
+        assert!(client.is_nft_eligible(&article_id, &xlm, &1_000_000));
+        assert!(!client.is_nft_eligible(&article_id, &usdc, &1_000_000));
+    }
+
+    #[test]
+    fn test_highlight_tips_count_toward_article() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+        client.tip_article(&tipper, &article_id, &xlm, &600_000);
+        client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl_1"), &article_id, &xlm, &300_000);
+        let asset_name = token::TokenClient::new(&env, &xlm).name();
+        let highlight_id = String::from_str(&env, "hl_2");
+        let receipt = client.tip_highlight_with_arweave(
+            &tipper,
+            &highlight_id,
+            &article_id,
+            &xlm,
+            &100_000,
+            &String::from_str(&env, "arweave_tx"),
+        );
+
+        // The Arweave event follows the tip it belongs to
+        let tip = TipEvent {
+            article_id: article_id.clone(),
+            tip_id: receipt.tip_id,
+            highlight_id: Some(highlight_id.clone()),
+            token: xlm.clone(),
+            tipper: tipper.clone(),
+            recipient: author.clone(),
+            gross: 100_000,
+            fee: 2_500,
+            net: 97_500,
+            payouts: vec![&env, RecipientPayout { recipient: author.clone(), amount: 97_500 }],
+            message: None,
+        };
+        let arweave = ArweaveTipEvent {
+            article_id: article_id.clone(),
+            tip_id: receipt.tip_id,
+            highlight_id: Some(highlight_id),
+            tipper: tipper.clone(),
+            author: author.clone(),
+            token: xlm.clone(),
+            amount: 100_000,
+            arweave_tx_id: String::from_str(&env, "arweave_tx"),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    xlm.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), author.clone(), asset_name.clone()).into_val(&env),
+                    97_500i128.into_val(&env),
+                ),
+                (
+                    xlm.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), contract_id.clone(), asset_name).into_val(&env),
+                    2_500i128.into_val(&env),
+                ),
+                (contract_id.clone(), tip.topics(&env), tip.data(&env)),
+                (contract_id.clone(), arweave.topics(&env), arweave.data(&env)),
+            ]
+        );
+
+        assert_eq!(client.get_article_total_tips(&article_id, &xlm), 1_000_000);
+        assert_eq!(client.get_article_direct_total(&article_id, &xlm), 600_000);
+        assert_eq!(client.get_article_highlight_total(&article_id, &xlm), 400_000);
+        assert_eq!(client.get_total_volume(&xlm), 1_000_000);
+        assert!(client.is_nft_eligible(&article_id, &xlm, &1_000_000));
+    }
+
+    #[test]
+    fn test_author_stats() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let (usdc, usdc_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &10_000_000);
+        usdc_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.add_allowed_token(&usdc, &50_000);
+
+        let empty = client.get_author_stats(&author);
+        assert_eq!(empty.tip_count, 0);
+        assert_eq!(empty.tokens.len(), 0);
+
+        client.register_article(&author, &symbol_short!("art1"));
+        client.register_article(&author, &symbol_short!("art2"));
+
+        env.ledger().set_timestamp(1_000);
+        client.tip_article(&tipper, &symbol_short!("art1"), &xlm, &1_000_000);
+        env.ledger().set_timestamp(2_000);
+        client.tip_article(&tipper, &symbol_short!("art2"), &usdc, &200_000);
+        env.ledger().set_timestamp(3_000);
+        client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl_1"), &symbol_short!("art2"), &xlm, &400_000);
+
+        let stats = client.get_author_stats(&author);
+        assert_eq!(stats.tip_count, 3);
+        assert_eq!(stats.first_tip_at, 1_000);
+        assert_eq!(stats.last_tip_at, 3_000);
+        assert_eq!(
+            stats.tokens,
+            vec![
+                &env,
+                AuthorTokenStats { token: xlm, tip_count: 2, gross: 1_400_000, net: 1_365_000, fees: 35_000 },
+                AuthorTokenStats { token: usdc, tip_count: 1, gross: 200_000, net: 195_000, fees: 5_000 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tipper_history_and_supporters() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let author_a = Address::generate(&env);
+        let author_b = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&alice, &10_000_000);
+        xlm_admin.mint(&bob, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.register_article(&author_a, &symbol_short!("art1"));
+        client.register_article(&author_b, &symbol_short!("art2"));
+
+        let first = client.tip_article(&alice, &symbol_short!("art1"), &xlm, &1_000_000);
+        let highlight_id = String::from_str(&env, "hl_1");
+        let second = client.tip_highlight_direct(&alice, &highlight_id, &symbol_short!("art2"), &xlm, &200_000);
+        client.tip_article(&alice, &symbol_short!("art1"), &xlm, &300_000);
+        client.tip_article(&bob, &symbol_short!("art1"), &xlm, &500_000);
+
+        let tip = client.get_tip(&second.tip_id).unwrap();
+        assert_eq!(tip.article_id, Some(symbol_short!("art2")));
+        assert_eq!(tip.highlight_id, Some(highlight_id));
+        assert_eq!(tip.author, author_b);
+        assert_eq!(tip.platform_fee, 5_000);
+        assert_eq!(client.get_tip(&99), None);
+
+        let stats = client.get_tipper_stats(&alice);
+        assert_eq!(stats.tip_count, 3);
+        assert_eq!(stats.authors_supported, 2);
+        assert_eq!(stats.given, vec![&env, TokenTotal { token: xlm.clone(), amount: 1_500_000 }]);
+
+        let oldest = client.get_tipper_tips(&alice, &0, &2);
+        assert_eq!(oldest.len(), 2);
+        assert_eq!(oldest.get(0).unwrap().tip_id, first.tip_id);
+        assert_eq!(oldest.get(1).unwrap().tip_id, second.tip_id);
+        let newest = client.get_latest_tipper_tips(&alice, &0, &1);
+        assert_eq!(newest.get(0).unwrap().amount, 300_000);
+
+        assert_eq!(client.get_supporter_count(&author_a), 2);
+        assert_eq!(
+            client.get_supporters(&author_a, &0, &10),
+            vec![
+                &env,
+                Supporter { tipper: alice.clone(), given: vec![&env, TokenTotal { token: xlm.clone(), amount: 1_300_000 }] },
+                Supporter { tipper: bob.clone(), given: vec![&env, TokenTotal { token: xlm.clone(), amount: 500_000 }] },
+            ]
+        );
+
+        // Ranked by amount; bob overtakes alice once their total is larger
+        assert_eq!(
+            client.get_top_supporters(&author_a, &xlm),
+            vec![
+                &env,
+                RankedSupporter { tipper: alice.clone(), amount: 1_300_000 },
+                RankedSupporter { tipper: bob.clone(), amount: 500_000 },
+            ]
+        );
+        client.tip_article(&bob, &symbol_short!("art1"), &xlm, &1_000_000);
+        assert_eq!(
+            client.get_top_supporters(&author_a, &xlm),
+            vec![
+                &env,
+                RankedSupporter { tipper: bob.clone(), amount: 1_500_000 },
+                RankedSupporter { tipper: alice.clone(), amount: 1_300_000 },
+            ]
+        );
+
+        // Only the top MAX_TOP_SUPPORTERS are kept
+        for _ in 0..MAX_TOP_SUPPORTERS {
+            let fan = Address::generate(&env);
+            xlm_admin.mint(&fan, &2_000_000);
+            client.tip_article(&fan, &symbol_short!("art1"), &xlm, &2_000_000);
+        }
+        let top = client.get_top_supporters(&author_a, &xlm);
+        assert_eq!(top.len(), MAX_TOP_SUPPORTERS);
+        assert!(top.iter().all(|entry| entry.amount == 2_000_000));
+        assert_eq!(client.get_supporter_count(&author_a), 2 + MAX_TOP_SUPPORTERS);
+    }
+
+    #[test]
+    fn test_tip_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author_a = Address::generate(&env);
+        let author_b = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.register_article(&author_a, &symbol_short!("art1"));
+        client.register_article(&author_b, &symbol_short!("art2"));
+
+        let items = vec![
+            &env,
+            TipItem {
+                article_id: symbol_short!("art1"),
+                highlight_id: None,
+                token: xlm.clone(),
+                amount: 1_000_000,
+                arweave_tx_id: Some(String::from_str(&env, "arweave_tx")),
+                message: None,
+                message_hash: None,
+            },
+            TipItem {
+                article_id: symbol_short!("art2"),
+                highlight_id: Some(String::from_str(&env, "hl_1")),
+                token: xlm.clone(),
+                amount: 400_000,
+                arweave_tx_id: None,
+                message: None,
+                message_hash: None,
+            },
+        ];
+        let receipts = client.tip_batch(&tipper, &items);
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts.get(0).unwrap().tip_id, 1);
+        assert_eq!(receipts.get(1).unwrap().tip_id, 2);
+        assert_eq!(xlm_client.balance(&author_a), 975_000);
+        assert_eq!(xlm_client.balance(&author_b), 390_000);
+        assert_eq!(client.get_article_tip_count(&symbol_short!("art1")), 1);
+        assert_eq!(client.get_highlight_tip_count(&String::from_str(&env, "hl_1")), 1);
+
+        // A failing item rolls back the whole batch
+        let items = vec![
+            &env,
+            TipItem {
+                article_id: symbol_short!("art1"),
+                highlight_id: None,
+                token: xlm.clone(),
+                amount: 1_000_000,
+                arweave_tx_id: None,
+                message: None,
+                message_hash: None,
+            },
+            TipItem {
+                article_id: symbol_short!("art2"),
+                highlight_id: None,
+                token: xlm.clone(),
+                amount: 1,
+                arweave_tx_id: None,
+                message: None,
+                message_hash: None,
+            },
+        ];
+        assert_eq!(client.try_tip_batch(&tipper, &items), Err(Ok(TippingError::BelowMinimumTip)));
+        assert_eq!(xlm_client.balance(&tipper), 8_600_000);
+        assert_eq!(client.get_article_tip_count(&symbol_short!("art1")), 1);
+
+        assert_eq!(client.try_tip_batch(&tipper, &vec![&env]), Err(Ok(TippingError::EmptyBatch)));
+        let mut too_many = vec![&env];
+        for _ in 0..=MAX_BATCH_SIZE {
+            too_many.push_back(items.get(0).unwrap());
+        }
+        assert_eq!(client.try_tip_batch(&tipper, &too_many), Err(Ok(TippingError::BatchTooLarge)));
+    }
+
+    #[test]
+    fn test_coauthor_splits() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let coauthor_b = Address::generate(&env);
+        let coauthor_c = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let article_id = symbol_short!("art1");
+        assert_eq!(
+            client.try_set_article_splits(&article_id, &vec![&env]),
+            Err(Ok(TippingError::ArticleNotRegistered))
+        );
+
+        client.register_article(&author, &article_id);
+        assert_eq!(
+            client.get_article_splits(&article_id),
+            vec![&env, SplitShare { recipient: author.clone(), share_bps: 10_000 }]
+        );
+
+        let share = |recipient: &Address, share_bps: u32| SplitShare { recipient: recipient.clone(), share_bps };
+        for invalid in [
+            vec![&env],
+            vec![&env, share(&author, 5_000), share(&coauthor_b, 4_000)],
+            vec![&env, share(&author, 5_000), share(&author, 5_000)],
+            vec![&env, share(&author, 10_000), share(&coauthor_b, 0)],
+        ] {
+            assert_eq!(
+                client.try_set_article_splits(&article_id, &invalid),
+                Err(Ok(TippingError::InvalidSplits))
+            );
+        }
+
+        let splits = vec![&env, share(&author, 5_000), share(&coauthor_b, 3_333), share(&coauthor_c, 1_667)];
+        client.set_article_splits(&article_id, &splits);
+        assert_eq!(client.get_article_splits(&article_id), splits);
+
+        // Rounding dust goes to the first recipient
+        let receipt = client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(receipt.author_received, 975_000);
+        assert_eq!(
+            receipt.payouts,
+            vec![
+                &env,
+                RecipientPayout { recipient: author.clone(), amount: 487_501 },
+                RecipientPayout { recipient: coauthor_b.clone(), amount: 324_967 },
+                RecipientPayout { recipient: coauthor_c.clone(), amount: 162_532 },
+            ]
+        );
+        assert_eq!(xlm_client.balance(&author), 487_501);
+        assert_eq!(xlm_client.balance(&coauthor_b), 324_967);
+        assert_eq!(xlm_client.balance(&coauthor_c), 162_532);
+        assert_eq!(client.get_accrued_fees(&xlm), 25_000);
+
+        // Highlight tips follow the same table
+        client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl_1"), &article_id, &xlm, &200_000);
+        assert_eq!(xlm_client.balance(&coauthor_c), 162_532 + 32_506);
+        let stats = client.get_author_stats(&coauthor_b);
+        assert_eq!(stats.tip_count, 2);
+        assert_eq!(stats.tokens.get(0).unwrap().net, 324_967 + 64_993);
+
+        // Changing the table needs every current member's signature
+        client.set_article_splits(&article_id, &vec![&env, share(&coauthor_b, 10_000)]);
+        let auths = env.auths();
+        assert_eq!(auths.len(), 3);
+        assert_eq!(auths[0].0, author);
+        assert_eq!(auths[1].0, coauthor_b);
+        assert_eq!(auths[2].0, coauthor_c);
+    }
+
+    #[test]
+    fn test_subscription_collection() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let subscriber = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&subscriber, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+
+        let month = 30 * 24 * 60 * 60;
+        assert_eq!(
+            client.try_subscribe(&subscriber, &author, &xlm, &1_000_000, &0, &3),
+            Err(Ok(TippingError::InvalidSubscription))
+        );
+        let id = client.subscribe(&subscriber, &author, &xlm, &1_000_000, &month, &3);
+        xlm_client.approve(&subscriber, &contract_id, &3_000_000, &(env.ledger().sequence() + 10_000));
+
+        // First period is due straight away
+        assert_eq!(client.collect_subscription(&id), 1);
+        assert_eq!(xlm_client.balance(&author), 975_000);
+        assert_eq!(xlm_client.balance(&contract_id), 25_000);
+        assert_eq!(client.get_accrued_fees(&xlm), 25_000);
+        assert_eq!(client.try_collect_subscription(&id), Err(Ok(TippingError::SubscriptionNotDue)));
+
+        // A keeper that falls behind catches up on every due period
+        env.ledger().with_mut(|ledger| ledger.timestamp += 2 * month);
+        assert_eq!(client.collect_subscription(&id), 2);
+        assert_eq!(xlm_client.balance(&author), 2_925_000);
+        let subscription = client.get_subscription(&id).unwrap();
+        assert_eq!(subscription.periods_charged, 3);
+        assert_eq!(subscription.status, SubscriptionStatus::Completed);
+        assert_eq!(client.get_author_stats(&author).tip_count, 3);
+        assert_eq!(client.get_total_volume(&xlm), 3_000_000);
+
+        // Each charge is in the subscriber's history and the author's supporters
+        let charges = client.get_tipper_tips(&subscriber, &0, &10);
+        assert_eq!(charges.len(), 3);
+        let charge = charges.get(2).unwrap();
+        assert_eq!(charge.article_id, None);
+        assert_eq!(charge.subscription_id, Some(id));
+        assert_eq!(charge.platform_fee, 25_000);
+        assert_eq!(client.get_tip(&charge.tip_id), Some(charge));
+        assert_eq!(client.get_tipper_stats(&subscriber).authors_supported, 1);
+        assert_eq!(
+            client.get_top_supporters(&author, &xlm),
+            vec![&env, RankedSupporter { tipper: subscriber.clone(), amount: 3_000_000 }]
+        );
+        assert_eq!(client.try_collect_subscription(&id), Err(Ok(TippingError::SubscriptionInactive)));
+        assert_eq!(client.try_collect_subscription(&99), Err(Ok(TippingError::SubscriptionNotFound)));
+
+        // Periods shorter than a day or longer than a year are rejected,
+        // and a long backlog is charged in steps
+        let day = MIN_SUBSCRIPTION_PERIOD_SECS;
+        assert_eq!(
+            client.try_subscribe(&subscriber, &author, &xlm, &100_000, &(day - 1), &20),
+            Err(Ok(TippingError::InvalidSubscription))
+        );
+        assert_eq!(
+            client.try_subscribe(&subscriber, &author, &xlm, &100_000, &(MAX_SUBSCRIPTION_PERIOD_SECS + 1), &20),
+            Err(Ok(TippingError::InvalidSubscription))
+        );
+        assert_eq!(
+            client.try_subscribe(&subscriber, &author, &xlm, &100_000, &u64::MAX, &20),
+            Err(Ok(TippingError::InvalidSubscription))
+        );
+        let id = client.subscribe(&subscriber, &author, &xlm, &100_000, &day, &20);
+        xlm_client.approve(&subscriber, &contract_id, &2_000_000, &(env.ledger().sequence() + 10_000));
+        env.ledger().with_mut(|ledger| ledger.timestamp += 30 * day);
+        assert_eq!(client.collect_subscription(&id), MAX_COLLECT_PERIODS);
+        let mut charged = MAX_COLLECT_PERIODS;
+        while charged < 20 {
+            let step = client.collect_subscription(&id);
+            assert_eq!(step, MAX_COLLECT_PERIODS.min(20 - charged));
+            charged += step;
+        }
+        assert_eq!(client.get_subscription(&id).unwrap().status, SubscriptionStatus::Completed);
+    }
+
+    #[test]
+    fn test_subscription_failures_and_lifecycle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let subscriber = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&subscriber, &1_500_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+
+        let month = 30 * 24 * 60 * 60;
+        let id = client.subscribe(&subscriber, &author, &xlm, &1_000_000, &month, &12);
+
+        // No allowance yet: nothing moves and the failure is reported
+        assert_eq!(client.collect_subscription(&id), 0);
+        let failed = SubscriptionChargeFailedEvent {
+            subscription_id: id,
+            period: 1,
+            reason: Symbol::new(&env, "insufficient_allowance"),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), failed.topics(&env), failed.data(&env))]
+        );
+
+        xlm_client.approve(&subscriber, &contract_id, &12_000_000, &(env.ledger().sequence() + 10_000));
+        assert_eq!(client.collect_subscription(&id), 1);
+
+        // Second period outruns the subscriber's balance
+        env.ledger().with_mut(|ledger| ledger.timestamp += month);
+        assert_eq!(client.collect_subscription(&id), 0);
+        let failed = SubscriptionChargeFailedEvent {
+            subscription_id: id,
+            period: 2,
+            reason: Symbol::new(&env, "insufficient_balance"),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), failed.topics(&env), failed.data(&env))]
+        );
+
+        // Paused subscriptions are not charged and skip the months they missed
+        client.pause_subscription(&id);
+        xlm_admin.mint(&subscriber, &10_000_000);
+        assert_eq!(client.try_collect_subscription(&id), Err(Ok(TippingError::SubscriptionInactive)));
+        env.ledger().with_mut(|ledger| ledger.timestamp += 3 * month);
+        client.resume_subscription(&id);
+        assert_eq!(client.collect_subscription(&id), 1);
+        assert_eq!(client.get_subscription(&id).unwrap().periods_charged, 2);
+
+        client.cancel_subscription(&id);
+        assert_eq!(client.get_subscription(&id).unwrap().status, SubscriptionStatus::Cancelled);
+        assert_eq!(client.try_resume_subscription(&id), Err(Ok(TippingError::SubscriptionInactive)));
+    }
+
+    #[test]
+    fn test_escrow_claimed_by_attested_author() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let impostor = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+
+        // Tips to an unregistered article are held by the contract
+        let article_id = symbol_short!("art1");
+        let receipt = client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(receipt.author_received, 0);
+        assert_eq!(receipt.payouts.len(), 0);
+        client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl_1"), &article_id, &xlm, &200_000);
+        assert_eq!(xlm_client.balance(&contract_id), 1_200_000);
+        assert_eq!(client.get_escrow_total(&article_id, &xlm), 1_200_000);
+        assert_eq!(client.get_escrowed_tips(&article_id, &0, &10).len(), 2);
+        assert_eq!(client.get_article_total_tips(&article_id, &xlm), 0);
+        assert_eq!(client.get_tip(&receipt.tip_id), None);
+
+        // Claiming needs the platform's attestation and pays out with the usual fee
+        assert_eq!(client.claim_escrow(&author, &article_id), 2);
+        let auths = env.auths();
+        assert_eq!(auths[0].0, author);
+        assert_eq!(auths[1].0, platform);
+
+        assert_eq!(client.get_article_author(&article_id), Some(author.clone()));
+        assert_eq!(xlm_client.balance(&author), 1_170_000);
+        assert_eq!(xlm_client.balance(&contract_id), 30_000);
+        assert_eq!(client.get_accrued_fees(&xlm), 30_000);
+        assert_eq!(client.get_escrow_total(&article_id, &xlm), 0);
+        assert_eq!(client.get_escrowed_tips(&article_id, &0, &10).len(), 0);
+        assert_eq!(client.get_article_total_tips(&article_id, &xlm), 1_200_000);
+        assert_eq!(client.get_article_tip_count(&article_id), 1);
+        assert_eq!(client.get_tip(&receipt.tip_id).unwrap().tipper, tipper);
+        assert_eq!(client.get_author_stats(&author).tip_count, 2);
+
+        // Nothing left to claim, and only the bound author may claim
+        assert_eq!(client.claim_escrow(&author, &article_id), 0);
+        assert_eq!(client.try_claim_escrow(&impostor, &article_id), Err(Ok(TippingError::Unauthorized)));
+
+        // Once registered, tips go straight to the author again
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(xlm_client.balance(&author), 2_145_000);
+    }
+
+    #[test]
+    fn test_expired_escrow_refunded() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+
+        let article_id = symbol_short!("art1");
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        let escrowed = client.get_escrowed_tips(&article_id, &0, &1).get(0).unwrap();
+        assert_eq!(escrowed.index, 0);
+
+        assert_eq!(client.try_refund_escrow(&article_id, &0), Err(Ok(TippingError::EscrowNotExpired)));
+
+        env.ledger().with_mut(|ledger| ledger.timestamp += ESCROW_EXPIRY_SECS);
+        client.refund_escrow(&article_id, &0);
+        assert_eq!(xlm_client.balance(&tipper), 10_000_000);
+        assert_eq!(client.get_escrow_total(&article_id, &xlm), 0);
+        assert_eq!(client.try_refund_escrow(&article_id, &0), Err(Ok(TippingError::EscrowNotFound)));
+    }
+
+    #[test]
+    fn test_pending_tip_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+
+        assert_eq!(
+            client.try_set_pending_window(&(MAX_PENDING_WINDOW + 1)),
+            Err(Ok(TippingError::InvalidPendingWindow))
+        );
+        client.set_pending_window(&100);
+
+        // The tipper can take a tip back inside the window
+        let receipt = client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(receipt.author_received, 0);
+        assert_eq!(xlm_client.balance(&contract_id), 1_000_000);
+        assert_eq!(client.get_pending_tip(&receipt.tip_id).unwrap().release_ledger, env.ledger().sequence() + 100);
+        client.cancel_pending_tip(&receipt.tip_id);
+        assert_eq!(xlm_client.balance(&tipper), 10_000_000);
+        assert_eq!(client.get_pending_tip(&receipt.tip_id), None);
+
+        // The admin can reverse a disputed one
+        let receipt = client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        client.reverse_pending_tip(&receipt.tip_id);
+        assert_eq!(xlm_client.balance(&tipper), 10_000_000);
+
+        // Otherwise it is released once the window closes
+        let receipt = client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl_1"), &article_id, &xlm, &1_000_000);
+        assert_eq!(client.try_finalize_tip(&receipt.tip_id), Err(Ok(TippingError::PendingWindowOpen)));
+        assert_eq!(xlm_client.balance(&author), 0);
+        assert_eq!(client.get_article_total_tips(&article_id, &xlm), 0);
+
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 100);
+        assert_eq!(client.try_cancel_pending_tip(&receipt.tip_id), Err(Ok(TippingError::PendingWindowClosed)));
+        assert_eq!(
+            client.try_reverse_pending_tip(&receipt.tip_id),
+            Err(Ok(TippingError::PendingWindowClosed))
+        );
+
+        let finalized = client.finalize_tip(&receipt.tip_id);
+        assert_eq!(finalized.tip_id, receipt.tip_id);
+        assert_eq!(finalized.author_received, 975_000);
+        assert_eq!(xlm_client.balance(&author), 975_000);
+        assert_eq!(xlm_client.balance(&contract_id), 25_000);
+        assert_eq!(client.get_accrued_fees(&xlm), 25_000);
+        assert_eq!(client.get_article_highlight_total(&article_id, &xlm), 1_000_000);
+        assert_eq!(client.try_finalize_tip(&receipt.tip_id), Err(Ok(TippingError::PendingTipNotFound)));
+
+        // Switching the window off pays out immediately again
+        client.set_pending_window(&0);
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(xlm_client.balance(&author), 1_950_000);
+    }
+
+    #[test]
+    fn test_tip_messages() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+        let asset_name = token::TokenClient::new(&env, &token).name();
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+
+        // Text message is stored and emitted with the tip
+        let text = TipMessage::Text(String::from_str(&env, "Great read!"));
+        let receipt = client.tip_article_with_message(&tipper, &article_id, &token, &1_000_000, &text);
+        let tip = TipEvent {
+            article_id: article_id.clone(),
+            tip_id: receipt.tip_id,
+            highlight_id: None,
+            token: token.clone(),
+            tipper: tipper.clone(),
+            recipient: author.clone(),
+            gross: 1_000_000,
+            fee: 25_000,
+            net: 975_000,
+            payouts: vec![&env, RecipientPayout { recipient: author.clone(), amount: 975_000 }],
+            message: Some(text.clone()),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    token.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), author.clone(), asset_name.clone()).into_val(&env),
+                    975_000i128.into_val(&env),
+                ),
+                (
+                    token.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), contract_id.clone(), asset_name).into_val(&env),
+                    25_000i128.into_val(&env),
+                ),
+                (contract_id.clone(), tip.topics(&env), tip.data(&env)),
+            ]
+        );
+        assert_eq!(client.get_tip_message(&receipt.tip_id), Some(text));
+
+        // Plain tips carry no message
+        let plain = client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        assert_eq!(client.get_tip_message(&plain.tip_id), None);
+
+        // Empty and oversized text is rejected
+        let empty = TipMessage::Text(String::from_str(&env, ""));
+        assert_eq!(
+            client.try_tip_article_with_message(&tipper, &article_id, &token, &1_000_000, &empty),
+            Err(Ok(TippingError::InvalidMessage))
+        );
+        let long = TipMessage::Text(String::from_bytes(&env, &[b'a'; 281]));
+        assert_eq!(
+            client.try_tip_article_with_message(&tipper, &article_id, &token, &1_000_000, &long),
+            Err(Ok(TippingError::InvalidMessage))
+        );
+
+        // Author turns off public text; hashes still go through
+        assert!(client.accepts_public_messages(&author));
+        client.set_public_messages(&author, &false);
+        let updated = PublicMessagesUpdatedEvent { author: author.clone(), enabled: false };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), updated.topics(&env), updated.data(&env))]
+        );
+        assert!(!client.accepts_public_messages(&author));
+
+        let highlight_id = String::from_str(&env, "hl1");
+        let text = TipMessage::Text(String::from_str(&env, "Nice"));
+        assert_eq!(
+            client.try_tip_highlight_with_message(&tipper, &highlight_id, &article_id, &token, &1_000_000, &text),
+            Err(Ok(TippingError::MessagesDisabled))
+        );
+        let hash = TipMessage::Hash(BytesN::from_array(&env, &[7; 32]));
+        let hashed = client.tip_highlight_with_message(&tipper, &highlight_id, &article_id, &token, &1_000_000, &hash);
+        assert_eq!(client.get_tip_message(&hashed.tip_id), Some(hash));
+
+        // Batch items take either text or a hash, not both
+        let both = TipItem {
+            article_id: article_id.clone(),
+            highlight_id: None,
+            token: token.clone(),
+            amount: 1_000_000,
+            arweave_tx_id: None,
+            message: Some(String::from_str(&env, "Hi")),
+            message_hash: Some(BytesN::from_array(&env, &[1; 32])),
+        };
+        assert_eq!(
+            client.try_tip_batch(&tipper, &vec![&env, both]),
+            Err(Ok(TippingError::InvalidMessage))
+        );
+
+        client.set_public_messages(&author, &true);
+        assert!(client.accepts_public_messages(&author));
+    }
+
+    #[test]
+    fn test_fee_schedule() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let partner = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+
+        let tier = |min_volume: i128, fee_bps: u32| FeeTier { min_volume, fee_bps };
+        assert_eq!(
+            client.try_set_fee_tiers(&admin, &vec![&env, tier(2_000_000, 200), tier(2_000_000, 100)]),
+            Err(Ok(TippingError::InvalidFeeTiers))
+        );
+        assert_eq!(
+            client.try_set_fee_tiers(&admin, &vec![&env, tier(0, 200)]),
+            Err(Ok(TippingError::InvalidFeeTiers))
+        );
+        assert_eq!(
+            client.try_set_fee_tiers(&admin, &vec![&env, tier(2_000_000, 1001)]),
+            Err(Ok(TippingError::FeeTooHigh))
+        );
+        let tiers = vec![&env, tier(2_000_000, 200), tier(3_000_000, 100)];
+        client.set_fee_tiers(&admin, &tiers);
+        let event = FeeTiersUpdatedEvent { tiers: tiers.clone() };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.get_fee_tiers(), tiers);
+
+        // Global fee until the author reaches the first tier
+        assert_eq!(client.quote_tip(&author, &1_000_000), FeeQuote { fee_bps: 250, fee: 25_000, net: 975_000 });
+        client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        assert_eq!(client.quote_tip(&author, &1_000_000).fee_bps, 200);
+        let receipt = client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        assert_eq!(receipt.platform_fee, 20_000);
+        assert_eq!(client.quote_tip(&author, &1_000_000).fee_bps, 100);
+
+        // Many minimum tips do not add up to a tier
+        let spammed = Address::generate(&env);
+        client.register_article(&spammed, &symbol_short!("art2"));
+        for _ in 0..5 {
+            client.tip_article(&tipper, &symbol_short!("art2"), &token, &MINIMUM_TIP_STROOPS);
+        }
+        assert_eq!(client.get_author_stats(&spammed).tip_count, 5);
+        assert_eq!(client.quote_tip(&spammed, &1_000_000).fee_bps, 250);
+
+        // Partner override wins over tiers
+        assert_eq!(
+            client.try_set_author_fee(&admin, &partner, &Some(1001)),
+            Err(Ok(TippingError::FeeTooHigh))
+        );
+        client.set_author_fee(&admin, &partner, &Some(50));
+        let event = AuthorFeeUpdatedEvent { author: partner.clone(), fee_bps: Some(50) };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.get_author_fee(&partner), Some(50));
+        assert_eq!(client.quote_tip(&partner, &1_000_000).fee, 5_000);
+
+        // Holiday caps fees only within its ledger range
+        let now = env.ledger().sequence();
+        assert_eq!(
+            client.try_set_fee_holiday(&admin, &(now + 10), &(now + 5), &0),
+            Err(Ok(TippingError::InvalidFeeHoliday))
+        );
+        client.set_fee_holiday(&admin, &(now + 10), &(now + 20), &0);
+        assert_eq!(client.quote_tip(&partner, &1_000_000).fee_bps, 50);
+        env.ledger().set_sequence_number(now + 10);
+        assert_eq!(client.quote_tip(&partner, &1_000_000).fee_bps, 0);
+        let receipt = client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        assert_eq!(receipt.platform_fee, 0);
+        assert_eq!(receipt.author_received, 1_000_000);
+        env.ledger().set_sequence_number(now + 21);
+        assert_eq!(client.quote_tip(&author, &1_000_000).fee_bps, 100);
+
+        client.clear_fee_holiday(&admin);
+        let event = FeeHolidayUpdatedEvent { holiday: None };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.get_fee_holiday(), None);
+
+        // Removing the override falls back to tiers, then the global fee
+        client.set_author_fee(&admin, &partner, &None);
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.quote_tip(&partner, &1_000_000).fee_bps, 250);
+        client.set_fee_tiers(&admin, &vec![&env]);
+        assert_eq!(client.quote_tip(&author, &1_000_000).fee_bps, 250);
+    }
+
+    #[test]
+    fn test_fee_change_timelock() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+
+        let now = env.ledger().sequence();
+        let delay = 7 * DAY_IN_LEDGERS;
+        assert_eq!(
+            client.get_fee_change_policy(),
+            FeeChangePolicy { delay_ledgers: delay, max_step_bps: 100 }
+        );
+
+        // Too soon, or too big a move
+        assert_eq!(
+            client.try_propose_fee_change(&admin, &300, &(now + delay - 1)),
+            Err(Ok(TippingError::InvalidFeeChange))
+        );
+        assert_eq!(
+            client.try_propose_fee_change(&admin, &351, &(now + delay)),
+            Err(Ok(TippingError::InvalidFeeChange))
+        );
+
+        // Cancelled proposals never apply
+        client.propose_fee_change(&admin, &350, &(now + delay));
+        assert_eq!(
+            client.get_pending_fee_change(),
+            Some(FeeChange { new_fee_bps: 350, effective_ledger: now + delay })
+        );
+        client.cancel_fee_change(&admin);
+        let event = FeeChangeCancelledEvent { new_fee_bps: 350, effective_ledger: now + delay };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.get_pending_fee_change(), None);
+        assert_eq!(client.try_cancel_fee_change(&admin), Err(Ok(TippingError::FeeChangeNotFound)));
+
+        // The old fee holds until the effective ledger, then the new one applies
+        client.propose_fee_change(&admin, &350, &(now + delay));
+        assert_eq!(client.try_activate_fee_change(), Err(Ok(TippingError::FeeChangeNotReady)));
+        env.ledger().set_sequence_number(now + delay - 1);
+        assert_eq!(client.tip_article(&tipper, &article_id, &token, &1_000_000).platform_fee, 25_000);
+        env.ledger().set_sequence_number(now + delay);
+        assert_eq!(client.get_platform_fee(), 350);
+        assert_eq!(client.try_cancel_fee_change(&admin), Err(Ok(TippingError::FeeChangeNotFound)));
+
+        // The first tip charged at the new fee settles it and announces it
+        assert_eq!(client.tip_article(&tipper, &article_id, &token, &1_000_000).platform_fee, 35_000);
+        let updated = FeeUpdatedEvent { old_fee_bps: 250, new_fee_bps: 350 };
+        assert_eq!(
+            env.events().all().slice(0..1),
+            vec![&env, (contract_id.clone(), updated.topics(&env), updated.data(&env))]
+        );
+        assert_eq!(client.get_pending_fee_change(), None);
+        assert_eq!(client.try_activate_fee_change(), Err(Ok(TippingError::FeeChangeNotFound)));
+        assert_eq!(client.get_platform_fee(), 350);
+
+        // Discounts never exceed the platform fee
+        client.set_author_fee(&admin, &author, &Some(500));
+        assert_eq!(client.quote_tip(&author, &1_000_000).fee_bps, 350);
+
+        // The policy only tightens
+        assert_eq!(
+            client.try_set_fee_change_policy(&(delay - 1), &100),
+            Err(Ok(TippingError::InvalidFeeChangePolicy))
+        );
+        assert_eq!(
+            client.try_set_fee_change_policy(&delay, &101),
+            Err(Ok(TippingError::InvalidFeeChangePolicy))
+        );
+        assert_eq!(
+            client.try_set_fee_change_policy(&delay, &0),
+            Err(Ok(TippingError::InvalidFeeChangePolicy))
+        );
+        client.set_fee_change_policy(&(2 * delay), &50);
+        let event = FeeChangePolicyUpdatedEvent { delay_ledgers: 2 * delay, max_step_bps: 50 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        let now = env.ledger().sequence();
+        assert_eq!(
+            client.try_propose_fee_change(&admin, &300, &(now + delay)),
+            Err(Ok(TippingError::InvalidFeeChange))
+        );
+        client.propose_fee_change(&admin, &300, &(now + 2 * delay));
+    }
+
+    #[test]
+    fn test_removed_token_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, _) = create_token(&env, &admin);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.add_allowed_token(&token, &MINIMUM_TIP_STROOPS);
+        client.register_article(&author, &symbol_short!("art1"));
+        assert!(client.is_token_allowed(&token));
+
+        client.remove_allowed_token(&token);
+        assert!(!client.is_token_allowed(&token));
+        assert_eq!(client.get_allowed_tokens(), vec![&env, xlm]);
+
+        let result = client.try_tip_article(&tipper, &symbol_short!("art1"), &token, &1_000_000);
+        assert_eq!(result, Err(Ok(TippingError::TokenNotAllowed)));
+        assert_eq!(client.try_remove_allowed_token(&token), Err(Ok(TippingError::TokenNotAllowed)));
+    }
+
+    #[test]
+    fn test_admin_errors() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let (xlm, _) = create_token(&env, &admin);
+
+        assert_eq!(
+            client.try_set_pending_window(&0),
+            Err(Err(InvokeError::Contract(OwnableError::OwnerNotSet as u32)))
+        );
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+
+        assert_eq!(
+            client.try_initialize(&admin, &platform, &xlm, &None),
+            Err(Ok(TippingError::AlreadyInitialized))
+        );
+        assert_eq!(client.try_propose_fee_change(&admin, &1_001, &0), Err(Ok(TippingError::FeeTooHigh)));
+        assert_eq!(
+            client.try_add_allowed_token(&xlm, &0),
+            Err(Ok(TippingError::InvalidMinimumTip))
+        );
+
+        // Owner-only entrypoints need the owner's signature
+        client.set_registration_cosign(&false);
+        assert_eq!(env.auths()[0].0, admin);
+        env.set_auths(&[]);
+        assert!(client.try_pause(&admin).is_err());
+    }
+
+    #[test]
+    fn test_ownership_transfer() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+        let (xlm, _) = create_token(&env, &admin);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+
+        // Two-step: nothing changes until the new owner accepts
+        let live_until = env.ledger().sequence() + DAY_IN_LEDGERS;
+        client.transfer_ownership(&new_owner, &live_until);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        client.accept_ownership();
+        assert_eq!(env.auths()[0].0, new_owner);
+        assert_eq!(client.get_owner(), Some(new_owner.clone()));
+
+        client.set_registration_cosign(&false);
+        assert_eq!(env.auths()[0].0, new_owner);
+
+        // The role admin moves with ownership, and only with it
+        let fee_manager = Symbol::new(&env, "fee_manager");
+        assert_eq!(client.get_admin(), Some(new_owner.clone()));
+        let unauthorized = Err(Ok(soroban_sdk::Error::from_contract_error(TippingError::Unauthorized as u32)));
+        assert_eq!(client.try_transfer_admin_role(&platform, &live_until), unauthorized);
+        assert_eq!(client.try_accept_admin_transfer(), unauthorized);
+        assert_eq!(client.try_renounce_admin(), unauthorized);
+        assert!(client.try_grant_role(&admin, &admin, &fee_manager).is_err());
+        client.grant_role(&new_owner, &platform, &fee_manager);
+
+        // An offer that is not accepted in time lapses
+        let stranger = Address::generate(&env);
+        client.transfer_ownership(&stranger, &(env.ledger().sequence() + DAY_IN_LEDGERS));
+        env.ledger().set_sequence_number(env.ledger().sequence() + DAY_IN_LEDGERS + 1);
+        assert!(client.try_accept_ownership().is_err());
+        assert_eq!(client.get_owner(), Some(new_owner.clone()));
+
+        // Renouncing locks owner-only entrypoints and role management for good
+        client.renounce_ownership();
+        assert_eq!(client.get_owner(), None);
+        assert_eq!(client.get_admin(), None);
+        assert_eq!(
+            client.try_set_pending_window(&0),
+            Err(Err(InvokeError::Contract(OwnableError::OwnerNotSet as u32)))
+        );
+        assert!(client.try_revoke_role(&new_owner, &platform, &fee_manager).is_err());
+
+        // Existing role members keep operating
+        client.set_fee_tiers(&platform, &vec![&env]);
+        assert_eq!(
+            client.try_initialize(&admin, &platform, &xlm, &None),
+            Err(Ok(TippingError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_roles() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let hot_key = Address::generate(&env);
+        let ops = Address::generate(&env);
+        let manager = Address::generate(&env);
+        let (xlm, _) = create_token(&env, &admin);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let pauser = Symbol::new(&env, "pauser");
+        let fee_manager = Symbol::new(&env, "fee_manager");
+        assert_eq!(client.get_admin(), Some(admin.clone()));
+        assert_eq!(client.has_role(&admin, &pauser), None);
+        assert_eq!(
+            client.try_pause(&admin),
+            Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)))
+        );
+
+        // A hot key can pause without holding any other power
+        client.grant_role(&admin, &hot_key, &pauser);
+        client.pause(&hot_key);
+        let event = PauseChangedEvent { caller: hot_key.clone(), paused: true };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (contract_id.clone(), Paused {}.topics(&env), Paused {}.data(&env)),
+                (contract_id.clone(), event.topics(&env), event.data(&env)),
+            ]
+        );
+        assert_eq!(
+            client.try_set_fee_tiers(&hot_key, &vec![&env]),
+            Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)))
+        );
+        client.unpause(&hot_key);
+
+        // Fee managers are administered by a "fee_admin" role
+        client.set_role_admin(&fee_manager, &Symbol::new(&env, "fee_admin"));
+        client.grant_role(&admin, &ops, &Symbol::new(&env, "fee_admin"));
+        client.grant_role(&ops, &manager, &fee_manager);
+        client.set_fee_tiers(&manager, &vec![&env, FeeTier { min_volume: 10_000_000, fee_bps: 200 }]);
+        assert_eq!(env.auths()[0].0, manager);
+        client.revoke_role(&ops, &manager, &fee_manager);
+        assert_eq!(
+            client.try_clear_fee_holiday(&manager),
+            Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)))
+        );
+
+        client.revoke_role(&admin, &hot_key, &pauser);
+        assert_eq!(client.get_role_member_count(&pauser), 0);
+        assert_eq!(
+            client.try_pause(&hot_key),
+            Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)))
+        );
+    }
+
+    #[test]
+    fn test_recompute_article_totals() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let art1 = symbol_short!("art1");
+        let art2 = symbol_short!("art2");
+
+        // The original tip_article added each tip to the total twice
+        let (contract_id, legacy, native) = create_v0_contract(&env);
+        let native_token = native.address.clone();
+        native.mint(&tipper, &10_000_000);
+        legacy.initialize(&admin, &platform, &Some(250));
+        legacy.tip_article(&tipper, &art1, &author, &2_000_000);
+        legacy.tip_article(&tipper, &art2, &author, &1_000_000);
+        assert_eq!(legacy.get_article_total_tips(&art1), 4_000_000);
+        assert_eq!(legacy.get_article_total_tips(&art2), 2_000_000);
+
+        install_current_code(&env, &contract_id);
+        let client = TippingContractClient::new(&env, &contract_id);
+        client.migrate();
+        client.register_article(&author, &art1);
+        client.register_article(&author, &art2);
+        let (other, other_admin) = create_token(&env, &admin);
+        other_admin.mint(&tipper, &10_000_000);
+        client.add_allowed_token(&other, &100_000);
+
+        client.tip_article(&tipper, &art1, &native_token, &1_000_000);
+        client.tip_highlight_direct(&tipper, &String::from_str(&env, "hl"), &art1, &other, &500_000);
+        assert_eq!(client.get_article_tip_count(&art1), 2);
+        assert_eq!(client.get_article_total_tips(&art1, &native_token), 5_000_000);
+        assert!(client.is_nft_eligible(&art1, &native_token, &4_000_000));
+
+        // Totals are rebuilt for every token they were kept in, allowed or not
+        client.remove_allowed_token(&native_token);
+        client.remove_allowed_token(&other);
+
+        let articles = vec![&env, art1.clone(), art2.clone()];
+        env.set_auths(&[]);
+        assert!(client.try_recompute_article_totals(&articles, &MAX_PAGE_SIZE).is_err());
+        env.mock_all_auths();
+
+        // Long histories are scanned over several calls; totals change as
+        // each article's scan ends
+        assert_eq!(client.recompute_article_totals(&articles, &1), articles);
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(env.events().all(), vec![&env]);
+        assert_eq!(client.get_article_total_tips(&art1, &native_token), 5_000_000);
+        assert_eq!(client.recompute_article_totals(&articles, &1), vec![&env, art2.clone()]);
+        let event = ArticleTotalCorrectedEvent {
+            article_id: art1.clone(),
+            token: native_token.clone(),
+            old_total: 5_000_000,
+            new_total: 3_000_000,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.recompute_article_totals(&vec![&env, art2.clone()], &MAX_PAGE_SIZE), vec![&env]);
+        let event = ArticleTotalCorrectedEvent {
+            article_id: art2.clone(),
+            token: native_token.clone(),
+            old_total: 2_000_000,
+            new_total: 1_000_000,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        assert_eq!(client.get_article_total_tips(&art1, &native_token), 3_000_000);
+        assert_eq!(client.get_article_total_tips(&art1, &other), 500_000);
+        assert_eq!(client.get_article_highlight_total(&art1, &other), 500_000);
+        assert_eq!(client.get_article_total_tips(&art2, &native_token), 1_000_000);
+        assert!(!client.is_nft_eligible(&art1, &native_token, &4_000_000));
+
+        // Correct totals are left alone
+        assert_eq!(client.recompute_article_totals(&articles, &MAX_PAGE_SIZE), vec![&env]);
+        assert_eq!(env.events().all(), vec![&env]);
+        assert_eq!(
+            client.try_recompute_article_totals(&vec![&env], &MAX_PAGE_SIZE),
+            Err(Ok(TippingError::EmptyBatch))
+        );
+        let mut too_many = vec![&env];
+        for _ in 0..=MAX_BATCH_SIZE {
+            too_many.push_back(art1.clone());
+        }
+        assert_eq!(
+            client.try_recompute_article_totals(&too_many, &MAX_PAGE_SIZE),
+            Err(Ok(TippingError::BatchTooLarge))
+        );
+    }
+
+    #[test]
+    fn test_fee_treasury() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let treasurer = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+        let asset_name = xlm_client.name();
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        client.tip_article(&tipper, &article_id, &xlm, &2_000_000);
+
+        // Fees stay in the contract until withdrawn
+        assert_eq!(client.get_accrued_fees(&xlm), 75_000);
+        assert_eq!(client.get_withdrawn_fees(&xlm), 0);
+        assert_eq!(xlm_client.balance(&contract_id), 75_000);
+        assert_eq!(xlm_client.balance(&platform), 0);
+
+        let unauthorized = Err(Err(InvokeError::Contract(AccessControlError::Unauthorized as u32)));
+        assert_eq!(client.try_withdraw_fees(&treasurer, &xlm, &platform, &10_000), unauthorized);
+        client.grant_role(&admin, &treasurer, &Symbol::new(&env, "treasurer"));
+        assert_eq!(
+            client.try_withdraw_fees(&treasurer, &xlm, &platform, &0),
+            Err(Ok(TippingError::InvalidFeeWithdrawal))
+        );
+        assert_eq!(
+            client.try_withdraw_fees(&treasurer, &xlm, &platform, &75_001),
+            Err(Ok(TippingError::InsufficientFees))
+        );
+
+        client.withdraw_fees(&treasurer, &xlm, &platform, &50_000);
+        let withdrawn = FeesWithdrawnEvent {
+            token: xlm.clone(),
+            caller: treasurer.clone(),
+            to: platform.clone(),
+            amount: 50_000,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    xlm.clone(),
+                    (symbol_short!("transfer"), contract_id.clone(), platform.clone(), asset_name).into_val(&env),
+                    50_000i128.into_val(&env),
+                ),
+                (contract_id.clone(), withdrawn.topics(&env), withdrawn.data(&env)),
+            ]
+        );
+        assert_eq!(env.auths()[0].0, treasurer);
+        assert_eq!(client.get_accrued_fees(&xlm), 25_000);
+        assert_eq!(client.get_withdrawn_fees(&xlm), 50_000);
+        assert_eq!(xlm_client.balance(&platform), 50_000);
+
+        // The platform address can be rotated by the owner
+        let new_platform = Address::generate(&env);
+        env.set_auths(&[]);
+        assert!(client.try_set_platform_address(&new_platform).is_err());
+        env.mock_all_auths();
+        client.set_platform_address(&new_platform);
+        let updated = PlatformAddressUpdatedEvent { old_address: platform, new_address: new_platform.clone() };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), updated.topics(&env), updated.data(&env))]
+        );
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.get_platform_address(), new_platform);
+
+        client.withdraw_fees(&treasurer, &xlm, &new_platform, &25_000);
+        assert_eq!(client.get_accrued_fees(&xlm), 0);
+        assert_eq!(client.get_withdrawn_fees(&xlm), 75_000);
+        assert_eq!(xlm_client.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_pause_guards_mutating_entrypoints() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "pauser"));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "treasurer"));
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+        client.set_pending_window(&DAY_IN_LEDGERS);
+        let pending = client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        client.pause(&admin);
+
+        // Every entrypoint that moves funds or writes user state is blocked
+        let enforced = InvokeError::Contract(PausableError::EnforcedPause as u32);
+        let highlight_id = String::from_str(&env, "hl");
+        let text = TipMessage::Text(String::from_str(&env, "hi"));
+        let tx_id = String::from_str(&env, "tx");
+        let item = TipItem {
+            article_id: article_id.clone(),
+            highlight_id: None,
+            token: xlm.clone(),
+            amount: 1_000_000,
+            arweave_tx_id: None,
+            message: None,
+            message_hash: None,
+        };
+        let splits = vec![&env, SplitShare { recipient: author.clone(), share_bps: 10_000 }];
+        assert_eq!(client.try_tip_article(&tipper, &article_id, &xlm, &1_000_000), Err(Err(enforced)));
+        assert_eq!(
+            client.try_tip_highlight_direct(&tipper, &highlight_id, &article_id, &xlm, &1_000_000),
+            Err(Err(enforced))
+        );
+        assert_eq!(
+            client.try_tip_article_with_message(&tipper, &article_id, &xlm, &1_000_000, &text),
+            Err(Err(enforced))
+        );
+        assert_eq!(
+            client.try_tip_highlight_with_message(&tipper, &highlight_id, &article_id, &xlm, &1_000_000, &text),
+            Err(Err(enforced))
+        );
+        assert_eq!(
+            client.try_tip_article_with_arweave(&tipper, &article_id, &xlm, &1_000_000, &tx_id),
+            Err(Err(enforced))
+        );
+        assert_eq!(
+            client.try_tip_highlight_with_arweave(&tipper, &highlight_id, &article_id, &xlm, &1_000_000, &tx_id),
+            Err(Err(enforced))
+        );
+        assert_eq!(client.try_tip_batch(&tipper, &vec![&env, item]), Err(Err(enforced)));
+        assert_eq!(client.try_register_article(&author, &symbol_short!("art2")), Err(Err(enforced)));
+        assert_eq!(client.try_set_article_splits(&article_id, &splits), Err(Err(enforced)));
+        assert_eq!(client.try_set_public_messages(&author, &false), Err(Err(enforced)));
+        assert_eq!(client.try_claim_escrow(&author, &article_id), Err(Err(enforced)));
+        assert_eq!(client.try_refund_escrow(&article_id, &0), Err(Err(enforced)));
+        assert_eq!(client.try_cancel_pending_tip(&pending.tip_id), Err(Err(enforced)));
+        assert_eq!(client.try_finalize_tip(&pending.tip_id), Err(Err(enforced)));
+        assert_eq!(client.try_subscribe(&tipper, &author, &xlm, &1_000_000, &3600, &3), Err(Err(enforced)));
+        assert_eq!(client.try_collect_subscription(&1), Err(Err(enforced)));
+        assert_eq!(client.try_pause_subscription(&1), Err(Err(enforced)));
+        assert_eq!(client.try_resume_subscription(&1), Err(Err(enforced)));
+        assert_eq!(client.try_cancel_subscription(&1), Err(Err(enforced)));
+        assert_eq!(client.try_activate_fee_change(), Err(Err(enforced)));
+        assert_eq!(client.try_migrate_article_tips(&article_id), Err(Err(enforced)));
+        assert_eq!(client.try_migrate_highlight_tips(&highlight_id), Err(Err(enforced)));
+        assert_eq!(client.try_withdraw_fees(&admin, &xlm, &platform, &1), Err(Err(enforced)));
+
+        // Recovery stays open: settings, reversals, repairs and upgrades
+        client.reverse_pending_tip(&pending.tip_id);
+        assert_eq!(xlm_client.balance(&tipper), 10_000_000);
+        client.set_pending_window(&0);
+        client.propose_fee_change(&admin, &300, &(env.ledger().sequence() + DEFAULT_FEE_CHANGE_DELAY));
+        client.cancel_fee_change(&admin);
+
+        // A due change is settled by the next proposal, though not by activate
+        client.propose_fee_change(&admin, &300, &(env.ledger().sequence() + DEFAULT_FEE_CHANGE_DELAY));
+        env.ledger().set_sequence_number(env.ledger().sequence() + DEFAULT_FEE_CHANGE_DELAY);
+        assert_eq!(client.try_activate_fee_change(), Err(Err(enforced)));
+        let effective_ledger = env.ledger().sequence() + DEFAULT_FEE_CHANGE_DELAY;
+        client.propose_fee_change(&admin, &350, &effective_ledger);
+        assert_eq!(client.get_platform_fee(), 300);
+        assert_eq!(client.get_pending_fee_change(), Some(FeeChange { new_fee_bps: 350, effective_ledger }));
+        client.cancel_fee_change(&admin);
+        assert_eq!(client.recompute_article_totals(&vec![&env, article_id.clone()], &MAX_PAGE_SIZE), vec![&env]);
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        client.bump_article(&article_id);
+        client.set_platform_address(&platform);
+        client.grant_role(&admin, &tipper, &Symbol::new(&env, "pauser"));
+
+        client.unpause(&tipper);
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(xlm_client.balance(&author), 970_000);
+    }
+
+    #[test]
+    fn test_tip_limits() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &100_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let art1 = symbol_short!("art1");
+        let art2 = symbol_short!("art2");
+        client.register_article(&author, &art1);
+        client.register_article(&author, &art2);
+
+        let limits = TipLimits {
+            window_ledgers: 100,
+            max_tips_per_window: 2,
+            max_tip: 5_000_000,
+            daily_cap: 8_000_000,
+        };
+        assert_eq!(
+            client.try_set_tip_limits(&xlm, &Some(TipLimits { window_ledgers: 0, ..limits.clone() })),
+            Err(Ok(TippingError::InvalidTipLimits))
+        );
+        env.set_auths(&[]);
+        assert!(client.try_set_tip_limits(&xlm, &Some(limits.clone())).is_err());
+        env.mock_all_auths();
+        client.set_tip_limits(&xlm, &Some(limits.clone()));
+        let event = TipLimitsUpdatedEvent { token: xlm.clone(), limits: Some(limits.clone()) };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        assert_eq!(client.get_tip_limits(&xlm), Some(limits));
+
+        // Single tips are capped
+        let exceeded = Err(Ok(TippingError::TipLimitExceeded));
+        assert_eq!(client.try_tip_article(&tipper, &art1, &xlm, &5_000_001), exceeded);
+
+        // Each article takes a limited number of tips per window from one tipper
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 1_000);
+        client.tip_article(&tipper, &art1, &xlm, &1_000_000);
+        client.tip_article(&tipper, &art1, &xlm, &1_000_000);
+        assert_eq!(client.try_tip_article(&tipper, &art1, &xlm, &1_000_000), exceeded);
+        client.tip_article(&tipper, &art2, &xlm, &1_000_000);
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 1_100);
+        client.tip_article(&tipper, &art1, &xlm, &1_000_000);
+        assert_eq!(client.get_daily_tipped(&tipper, &xlm), 4_000_000);
+
+        // The daily cap covers every article, batches included
+        let item = TipItem {
+            article_id: art2.clone(),
+            highlight_id: None,
+            token: xlm.clone(),
+            amount: 5_000_000,
+            arweave_tx_id: None,
+            message: None,
+            message_hash: None,
+        };
+        assert_eq!(
+            client.try_tip_batch(&tipper, &vec![&env, item]),
+            Err(Ok(TippingError::TipLimitExceeded))
+        );
+        client.tip_article(&tipper, &art2, &xlm, &4_000_000);
+        assert_eq!(client.get_daily_tipped(&tipper, &xlm), 8_000_000);
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 1_300);
+        assert_eq!(client.try_tip_article(&tipper, &art2, &xlm, &1_000_000), exceeded);
+
+        // A day later the usage has rolled off
+        env.ledger().with_mut(|ledger| ledger.sequence_number = 1_100 + DAY_IN_LEDGERS + SPEND_BUCKET_LEDGERS);
+        assert_eq!(client.get_daily_tipped(&tipper, &xlm), 0);
+        client.tip_article(&tipper, &art2, &xlm, &5_000_000);
+
+        client.set_tip_limits(&xlm, &None);
+        assert_eq!(client.get_tip_limits(&xlm), None);
+        client.tip_article(&tipper, &art2, &xlm, &10_000_000);
+    }
+
+    #[test]
+    fn test_squatted_article_reassigned() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let squatter = Address::generate(&env);
+        let accomplice = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        let xlm_client = token::TokenClient::new(&env, &xlm);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
+        let article_id = symbol_short!("art1");
+
+        // Without co-signing, anyone can claim someone else's article first
+        client.register_article(&squatter, &article_id);
+        client.set_article_splits(
+            &article_id,
+            &vec![
+                &env,
+                SplitShare { recipient: squatter.clone(), share_bps: 5_000 },
+                SplitShare { recipient: accomplice.clone(), share_bps: 5_000 },
+            ],
+        );
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(xlm_client.balance(&squatter), 487_500);
+        assert_eq!(
+            client.try_register_article(&author, &article_id),
+            Err(Ok(TippingError::ArticleAlreadyRegistered))
+        );
+
+        // The platform attests the real author, who signs for the payout address
+        env.set_auths(&[]);
+        assert!(client.try_reassign_article(&article_id, &author).is_err());
+        env.mock_all_auths();
+        client.reassign_article(&article_id, &author);
+        let event = ArticleReassignedEvent {
+            article_id: article_id.clone(),
+            old_author: squatter.clone(),
+            new_author: author.clone(),
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+        let auths = env.auths();
+        assert_eq!(auths[0].0, platform);
+        assert_eq!(auths[1].0, author);
+
+        // The squatter's split table goes with the registration
+        assert_eq!(client.get_article_author(&article_id), Some(author.clone()));
+        assert_eq!(
+            client.get_article_splits(&article_id),
+            vec![&env, SplitShare { recipient: author.clone(), share_bps: 10_000 }]
+        );
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(xlm_client.balance(&author), 975_000);
+        assert_eq!(xlm_client.balance(&squatter), 487_500);
+
+        assert_eq!(
+            client.try_reassign_article(&symbol_short!("art2"), &author),
+            Err(Ok(TippingError::ArticleNotRegistered))
+        );
+    }
+
+    #[test]
+    fn test_tips_routed_to_registered_author() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let impostor = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+
+        let article_id = symbol_short!("art1");
+        let highlight_id = String::from_str(&env, "highlight_abc");
+
+        // Arweave tips report the author, so they need a registered article
+        assert_eq!(
+            client.try_tip_article_with_arweave(&tipper, &article_id, &token, &1_000_000, &String::from_str(&env, "tx")),
+            Err(Ok(TippingError::ArticleNotRegistered))
+        );
+        assert_eq!(client.get_article_author(&article_id), None);
+
+        client.register_article(&author, &article_id);
+        assert_eq!(client.get_article_author(&article_id), Some(author.clone()));
+
+        // First registration wins
+        assert_eq!(
+            client.try_register_article(&impostor, &article_id),
+            Err(Ok(TippingError::ArticleAlreadyRegistered))
+        );
+
+        client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        client.tip_highlight_direct(&tipper, &highlight_id, &article_id, &token, &1_000_000);
+
+        assert_eq!(client.get_balance(&author), 1_950_000);
+        assert_eq!(client.get_balance(&impostor), 0);
+    }
+
+    #[test]
+    fn test_registration_platform_cosign() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, _) = create_token(&env, &admin);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
 
-        if admin != stored_admin {
-            panic!("Unauthorized");
-        }
+        client.register_article(&author, &symbol_short!("art1"));
+        assert_eq!(env.auths().len(), 1);
 
-        if new_fee_bps > 1000 { // Max 10%
-            panic!("Fee too high");
-        }
+        client.set_registration_cosign(&true);
+        client.register_article(&author, &symbol_short!("art2"));
 
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformFeeBps, &new_fee_bps);
+        let signers: Vec<Address> = Vec::from_iter(&env, env.auths().iter().map(|(signer, _)| signer.clone()));
+        assert_eq!(signers, vec![&env, author, platform]);
     }
 
-    /// Tip a highlight directly (same flow as tip_article)
-    pub fn tip_highlight_direct(
-        env: Env,
-        tipper: Address,
-        highlight_id: String,
-        article_id: Symbol,
-        author: Address,
-        amount: i128,
-    ) -> TipReceipt {
-        tipper.require_auth();
+    #[test]
+    fn test_paginated_tip_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &100_000_000);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
 
-        // Validate minimum amount
-        if amount < MINIMUM_TIP_STROOPS {
-            panic!("Amount below minimum tip");
+        let article_id = symbol_short!("art1");
+        let highlight_id = String::from_str(&env, "highlight_abc");
+        client.register_article(&author, &article_id);
+
+        for amount in [1_000_000, 2_000_000, 3_000_000] {
+            client.tip_article(&tipper, &article_id, &token, &amount);
+            client.tip_highlight_direct(&tipper, &highlight_id, &article_id, &token, &amount);
         }
+        assert_eq!(client.get_article_tip_count(&article_id), 3);
+        assert_eq!(client.get_highlight_tip_count(&highlight_id), 3);
 
-        // Get platform settings (reuse existing code)
-        let platform_address: Address = env.storage()
-            .instance()
-            .get(&DataKey::PlatformAddress)
-            .expect("Platform address not set");
+        // Oldest first
+        let page = client.get_article_tips(&article_id, &0, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().amount, 1_000_000);
+        assert_eq!(page.get(1).unwrap().amount, 2_000_000);
 
-        let platform_fee_bps: u32 = env.storage()
-            .instance()
-            .get(&DataKey::PlatformFeeBps)
-            .unwrap_or(DEFAULT_PLATFORM_FEE_BPS);
+        let page = client.get_article_tips(&article_id, &2, &2);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().amount, 3_000_000);
+        assert_eq!(client.get_article_tips(&article_id, &5, &2).len(), 0);
 
-        // Calculate fees (same as tip_article)
-        let platform_fee = (amount * platform_fee_bps as i128) / 10_000;
-        let author_share = amount - platform_fee;
+        // Newest first
+        let page = client.get_latest_article_tips(&article_id, &0, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().amount, 3_000_000);
+        assert_eq!(page.get(1).unwrap().amount, 2_000_000);
 
-        // Get XLM token client (same as tip_article)
-        let xlm_address = Address::from_string(&String::from_str(&env, XLM_TOKEN_ADDRESS));
-        let xlm_client = token::TokenClient::new(&env, &xlm_address);
+        let page = client.get_latest_highlight_tips(&highlight_id, &2, &10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().amount, 1_000_000);
+        assert_eq!(client.get_highlight_tips(&highlight_id, &0, &10).len(), 3);
+    }
 
-        // Transfer author's share
-        xlm_client.transfer(&tipper, &author, &author_share);
+    #[test]
+    fn test_legacy_tip_migration() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Transfer platform fee
-        if platform_fee > 0 {
-            xlm_client.transfer(&tipper, &platform_address, &platform_fee);
-        }
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
 
-        // Get and increment tip counter (same as tip_article)
-        let tip_counter: u64 = env.storage()
-            .persistent()
-            .get(&DataKey::TipCounter)
-            .unwrap_or(0);
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &xlm, &Some(250));
 
-        let new_tip_id = tip_counter + 1;
-        env.storage().persistent().set(&DataKey::TipCounter, &new_tip_id);
+        let article_id = symbol_short!("art1");
+        let quiet_article = symbol_short!("art2");
+        let highlight_id = String::from_str(&env, "highlight_abc");
+        client.register_article(&author, &article_id);
 
-        // Store highlight tip
-        let tip = HighlightTip {
+        // Vectors as written by the original deployment
+        let legacy_tip = LegacySimpleTip { tipper: tipper.clone(), amount: 500_000, timestamp: 1 };
+        let legacy_highlight_tip = LegacyHighlightTip {
             highlight_id: highlight_id.clone(),
-            article_id,
+            article_id: article_id.clone(),
             tipper: tipper.clone(),
-            amount,
-            timestamp: env.ledger().timestamp(),
+            amount: 700_000,
+            timestamp: 2,
         };
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &LegacyDataKey::ArticleTips(article_id.clone()),
+                &vec![&env, legacy_tip.clone(), legacy_tip.clone()],
+            );
+            env.storage().persistent().set(
+                &LegacyDataKey::ArticleTips(quiet_article.clone()),
+                &vec![&env, legacy_tip.clone()],
+            );
+            env.storage().persistent().set(
+                &LegacyDataKey::HighlightTips(highlight_id.clone()),
+                &vec![&env, legacy_highlight_tip.clone()],
+            );
+        });
 
-        // Get existing tips for highlight
-        let mut highlight_tips: Vec<HighlightTip> = env.storage()
-            .persistent()
-            .get(&DataKey::HighlightTips(highlight_id.clone()))
-            .unwrap_or(vec![&env]);
+        // Unmigrated history is readable as is
+        assert_eq!(client.get_article_tip_count(&quiet_article), 1);
+        assert_eq!(client.get_article_tips(&quiet_article, &0, &10).get(0).unwrap().token, xlm);
 
-        highlight_tips.push_back(tip);
+        // New tips append after the legacy history without moving it
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        let tips = client.get_article_tips(&article_id, &0, &10);
+        assert_eq!(tips.len(), 3);
+        assert_eq!(tips.get(0).unwrap().amount, 500_000);
+        assert_eq!(tips.get(0).unwrap().token, xlm);
+        assert_eq!(tips.get(2).unwrap().amount, 1_000_000);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::HighlightTips(highlight_id), &highlight_tips);
+        // Histories are moved explicitly, and only once
+        assert_eq!(client.migrate_article_tips(&article_id), 0);
+        assert_eq!(client.get_article_tips(&article_id, &0, &10), tips);
+        assert_eq!(client.migrate_article_tips(&quiet_article), 0);
+        assert_eq!(client.migrate_article_tips(&quiet_article), 0);
+        assert_eq!(client.get_article_tip_count(&quiet_article), 1);
 
-        // Create receipt (same format as tip_article)
-        TipReceipt {
-            tip_id: new_tip_id,
-            amount_sent: amount,
-            author_received: author_share,
-            platform_fee,
-            timestamp: env.ledger().timestamp(),
-        }
-    }
+        assert_eq!(client.migrate_highlight_tips(&highlight_id), 0);
+        let highlight_tips = client.get_highlight_tips(&highlight_id, &0, &10);
+        assert_eq!(highlight_tips.get(0).unwrap().amount, 700_000);
+        assert_eq!(highlight_tips.get(0).unwrap().token, xlm);
 
-    /// Get all tips for a highlight
-    pub fn get_highlight_tips(env: Env, highlight_id: String) -> Vec<HighlightTip> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::HighlightTips(highlight_id))
-            .unwrap_or(vec![&env])
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().persistent().has(&LegacyDataKey::ArticleTips(article_id.clone())));
+            assert!(!env.storage().persistent().has(&LegacyDataKey::HighlightTips(highlight_id.clone())));
+        });
     }
 
-    // ========== PAUSABLE PATTERN (OZ) ==========
+    #[test]
+    fn test_large_legacy_tip_migration() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-    /// Check if contract is paused
-    pub fn is_paused(env: Env) -> bool {
-        pausable::paused(&env)
-    }
+        let contract_id = env.register(TippingContract, ());
+        let client = TippingContractClient::new(&env, &contract_id);
 
-    /// Pause the contract (admin only)
-    pub fn pause(env: Env, admin: Address) {
-        admin.require_auth();
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (xlm, xlm_admin) = create_token(&env, &admin);
+        xlm_admin.mint(&tipper, &10_000_000);
 
-        let stored_admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+        client.initialize(&admin, &platform, &xlm, &Some(250));
 
-        if admin != stored_admin {
-            panic!("Unauthorized");
-        }
+        let article_id = symbol_short!("popular");
+        client.register_article(&author, &article_id);
 
-        pausable::pause(&env);
-    }
+        // Far more history than one call may rewrite
+        let legacy_len = 5 * MAX_MIGRATION_PAGE;
+        let mut legacy_tips = vec![&env];
+        for index in 0..legacy_len {
+            legacy_tips.push_back(LegacySimpleTip {
+                tipper: tipper.clone(),
+                amount: 100_000 + index as i128,
+                timestamp: index as u64,
+            });
+        }
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(&LegacyDataKey::ArticleTips(article_id.clone()), &legacy_tips);
+        });
 
-    /// Unpause the contract (admin only)
-    pub fn unpause(env: Env, admin: Address) {
-        admin.require_auth();
+        // Tipping only reserves the legacy range, then appends behind it
+        client.tip_article(&tipper, &article_id, &xlm, &1_000_000);
+        assert_eq!(client.get_article_tip_count(&article_id), legacy_len + 1);
+        assert_eq!(client.get_latest_article_tips(&article_id, &0, &1).get(0).unwrap().amount, 1_000_000);
 
-        let stored_admin: Address = env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+        // Each call moves one page, and reads stay complete throughout
+        let mut remaining = legacy_len;
+        while remaining > 0 {
+            let page = client.get_article_tips(&article_id, &(legacy_len - remaining), &MAX_MIGRATION_PAGE);
+            assert_eq!(page.len(), MAX_MIGRATION_PAGE);
+            assert_eq!(page.get(0).unwrap().amount, 100_000 + (legacy_len - remaining) as i128);
 
-        if admin != stored_admin {
-            panic!("Unauthorized");
+            let next = client.migrate_article_tips(&article_id);
+            assert_eq!(next, remaining - MAX_MIGRATION_PAGE);
+            remaining = next;
         }
 
-        pausable::unpause(&env);
+        let tips = client.get_article_tips(&article_id, &(legacy_len - 1), &2);
+        assert_eq!(tips.get(0).unwrap().amount, 100_000 + (legacy_len - 1) as i128);
+        assert_eq!(tips.get(1).unwrap().amount, 1_000_000);
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().persistent().has(&LegacyDataKey::ArticleTips(article_id.clone())));
+            assert!(!env.storage().persistent().has(&DataKey::LegacyTipCursor(article_id.clone())));
+        });
+        assert_eq!(client.migrate_article_tips(&article_id), 0);
     }
 
-    // ========== ARWEAVE-ENABLED TIPPING ==========
+    #[test]
+    fn test_upgrade_and_migrate() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-    /// Tip an article with Arweave reference
-    pub fn tip_article_with_arweave(
-        env: Env,
-        tipper: Address,
-        article_id: Symbol,
-        author: Address,
-        amount: i128,
-        arweave_tx_id: String,
-    ) -> TipReceipt {
-        // Check not paused
-        pausable::when_not_paused(&env);
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let article_id = symbol_short!("art1");
+        let highlight_id = String::from_str(&env, "hl1");
 
-        // Execute normal tip
-        let receipt = Self::tip_article(env.clone(), tipper.clone(), article_id.clone(), author.clone(), amount);
+        // State written by the original release through its own entrypoints
+        let (contract_id, legacy, native) = create_v0_contract(&env);
+        let native_token = native.address.clone();
+        native.mint(&tipper, &10_000_000);
+        legacy.initialize(&admin, &platform, &Some(250));
+        legacy.tip_article(&tipper, &article_id, &author, &1_000_000);
+        legacy.tip_article(&tipper, &article_id, &author, &2_000_000);
+        legacy.tip_highlight_direct(&tipper, &highlight_id, &article_id, &author, &500_000);
+        assert_eq!(legacy.get_total_volume(), 3_000_000);
+        assert_eq!(legacy.get_article_total_tips(&article_id), 6_000_000);
+        assert_eq!(native.balance(&author), 3_412_500);
 
-        // Emit event with Arweave TX ID
-        env.events().publish(
-            (Symbol::new(&env, "tip_with_arweave"), article_id),
-            (tipper, author, amount, arweave_tx_id)
+        install_current_code(&env, &contract_id);
+        let client = TippingContractClient::new(&env, &contract_id);
+        assert_eq!(client.get_schema_version(), 0);
+        assert_eq!(client.get_owner(), None);
+        assert_eq!(
+            client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+            Err(Err(InvokeError::Contract(OwnableError::OwnerNotSet as u32)))
         );
 
-        receipt
-    }
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        let event = MigratedEvent { from_version: 0, to_version: SCHEMA_VERSION };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
 
-    /// Tip a highlight with Arweave reference
-    pub fn tip_highlight_with_arweave(
-        env: Env,
-        tipper: Address,
-        highlight_id: String,
-        article_id: Symbol,
-        author: Address,
-        amount: i128,
-        arweave_tx_id: String,
-    ) -> TipReceipt {
-        // Check not paused
-        pausable::when_not_paused(&env);
+        // Running it again changes nothing
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(env.events().all(), vec![&env]);
 
-        // Execute normal highlight tip
-        let receipt = Self::tip_highlight_direct(
-            env.clone(),
-            tipper.clone(),
-            highlight_id.clone(),
-            article_id.clone(),
-            author.clone(),
-            amount
-        );
+        assert_eq!(client.get_schema_version(), SCHEMA_VERSION);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        assert_eq!(client.get_admin(), Some(admin.clone()));
+        assert_eq!(client.get_native_token(), native_token);
+        assert_eq!(client.get_min_tip(&native_token), MINIMUM_TIP_STROOPS);
+        assert_eq!(client.get_platform_fee(), 250);
+        assert_eq!(client.get_total_volume(&native_token), 3_000_000);
+        assert_eq!(client.get_balance(&author), 3_412_500);
 
-        // Emit event with Arweave TX ID
-        env.events().publish(
-            (Symbol::new(&env, "highlight_tip_arweave"), highlight_id),
-            (tipper, author, amount, arweave_tx_id)
+        // Totals carry over as stored, still counting each article tip twice
+        // until recompute_article_totals repairs them
+        assert_eq!(client.get_article_total_tips(&article_id, &native_token), 6_000_000);
+
+        // Tip history reads through before and after it moves to indexed entries
+        let amounts = |tips: Vec<SimpleTip>| {
+            let mut amounts = vec![&env];
+            for tip in tips.iter() {
+                amounts.push_back(tip.amount);
+            }
+            amounts
+        };
+        let legacy_amounts = vec![&env, 1_000_000i128, 2_000_000];
+        assert_eq!(amounts(client.get_article_tips(&article_id, &0, &MAX_PAGE_SIZE)), legacy_amounts);
+        assert_eq!(client.migrate_article_tips(&article_id), 0);
+        assert_eq!(client.migrate_highlight_tips(&highlight_id), 0);
+        assert_eq!(amounts(client.get_article_tips(&article_id, &0, &MAX_PAGE_SIZE)), legacy_amounts);
+        let highlight_tips = client.get_highlight_tips(&highlight_id, &0, &MAX_PAGE_SIZE);
+        assert_eq!(highlight_tips.len(), 1);
+        assert_eq!(highlight_tips.get_unchecked(0).amount, 500_000);
+        assert_eq!(highlight_tips.get_unchecked(0).token, native_token);
+        assert_eq!(client.get_article_total_tips(&article_id, &native_token), 6_000_000);
+
+        // New tips append after the carried-over history
+        client.register_article(&author, &article_id);
+        client.tip_article(&tipper, &article_id, &native_token, &1_000_000);
+        assert_eq!(client.get_article_tip_count(&article_id), 3);
+        assert_eq!(client.get_article_total_tips(&article_id, &native_token), 7_000_000);
+        assert_eq!(client.get_total_volume(&native_token), 4_000_000);
+
+        // Upgrades need code that has been uploaded
+        assert!(client.try_upgrade(&BytesN::from_array(&env, &[0; 32])).is_err());
+
+        // Later upgrades go through the owner-gated entrypoint
+        let wasm_hash = env.deployer().upload_contract_wasm(TIPPING_WASM);
+        env.set_auths(&[]);
+        assert!(client.try_upgrade(&wasm_hash).is_err());
+        env.mock_all_auths();
+        client.upgrade(&wasm_hash);
+        assert_eq!(env.auths()[0].0, admin);
+        let upgraded = UpgradedEvent { wasm_hash };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), upgraded.topics(&env), upgraded.data(&env))]
         );
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(env.events().all(), vec![&env]);
+        assert_eq!(client.get_owner(), Some(admin.clone()));
+        assert_eq!(client.get_article_tip_count(&article_id), 3);
+        assert_eq!(client.get_article_total_tips(&article_id, &native_token), 7_000_000);
+        assert_eq!(client.get_total_volume(&native_token), 4_000_000);
 
-        receipt
+        // Fresh deployments start at the current schema
+        let fresh_id = env.register(TippingContract, ());
+        let fresh = TippingContractClient::new(&env, &fresh_id);
+        assert_eq!(fresh.try_migrate(), Err(Ok(TippingError::NotInitialized)));
+        let (xlm, _) = create_token(&env, &admin);
+        fresh.initialize(&admin, &platform, &xlm, &None);
+        assert_eq!(fresh.get_schema_version(), SCHEMA_VERSION);
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, symbol_short, Env};
 
     #[test]
-    fn test_initialize() {
+    fn test_ttl_extended_and_bumped() {
         let env = Env::default();
         env.mock_all_auths();
-        
+
         let contract_id = env.register(TippingContract, ());
         let client = TippingContractClient::new(&env, &contract_id);
-        
+
         let admin = Address::generate(&env);
         let platform = Address::generate(&env);
-        
-        client.initialize(&admin, &platform, &Some(250));
-        
-        // Verify initialization
-        let volume = client.get_total_volume();
-        assert_eq!(volume, 0);
+        let tipper = Address::generate(&env);
+        let author = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+
+        let (other, other_admin) = create_token(&env, &admin);
+        other_admin.mint(&tipper, &10_000_000);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.add_allowed_token(&other, &100_000);
+
+        let article_id = symbol_short!("art1");
+        let highlight_id = String::from_str(&env, "hl1");
+        client.register_article(&author, &article_id);
+        client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        client.tip_highlight_direct(&tipper, &highlight_id, &article_id, &other, &1_000_000);
+        client.remove_allowed_token(&other);
+
+        let total_key = DataKey::ArticleTotalTips(article_id.clone(), token.clone());
+        let other_total_key = DataKey::ArticleTotalTips(article_id.clone(), other.clone());
+        let author_key = DataKey::ArticleAuthor(article_id.clone());
+        let history_keys = [
+            DataKey::ArticleTip(article_id.clone(), 0),
+            DataKey::HighlightTip(highlight_id.clone(), 0),
+            DataKey::TipperTip(tipper.clone(), 0),
+            DataKey::Tip(1),
+            DataKey::TipperTip(tipper.clone(), 1),
+            DataKey::Tip(2),
+        ];
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&total_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&author_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().instance().get_ttl(), DEFAULT_TTL_EXTEND_TO);
+        });
+
+        // Far past the network's minimum persistent TTL, the data is still live
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 25 * DAY_IN_LEDGERS);
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&total_key), 5 * DAY_IN_LEDGERS);
+        });
+        assert_eq!(client.get_article_author(&article_id), Some(author));
+
+        // Reading a total extends it
+        assert_eq!(client.get_article_total_tips(&article_id, &token), 1_000_000);
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&total_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&other_total_key), 5 * DAY_IN_LEDGERS);
+            assert_eq!(env.storage().persistent().get_ttl(&author_key), 5 * DAY_IN_LEDGERS);
+        });
+
+        // The keeper bump pushes the rest back out, removed tokens included
+        client.bump_article(&article_id);
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().persistent().get_ttl(&other_total_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().persistent().get_ttl(&author_key), DEFAULT_TTL_EXTEND_TO);
+            assert_eq!(env.storage().instance().get_ttl(), DEFAULT_TTL_EXTEND_TO);
+            for key in history_keys.iter() {
+                assert_eq!(env.storage().persistent().get_ttl(key), 5 * DAY_IN_LEDGERS);
+            }
+        });
+
+        // History is bumped a page at a time
+        client.bump_article_tips(&article_id, &0, &MAX_PAGE_SIZE);
+        client.bump_highlight_tips(&highlight_id, &0, &MAX_PAGE_SIZE);
+        client.bump_tipper_tips(&tipper, &0, &MAX_PAGE_SIZE);
+        env.as_contract(&contract_id, || {
+            for key in history_keys.iter() {
+                assert_eq!(env.storage().persistent().get_ttl(key), DEFAULT_TTL_EXTEND_TO);
+            }
+        });
     }
-    
+
     #[test]
-    fn test_tip_article() {
+    fn test_ttl_config() {
         let env = Env::default();
         env.mock_all_auths();
-        
+
         let contract_id = env.register(TippingContract, ());
         let client = TippingContractClient::new(&env, &contract_id);
-        
+
         let admin = Address::generate(&env);
         let platform = Address::generate(&env);
-        let tipper = Address::generate(&env);
-        let author = Address::generate(&env);
-        
-        // Initialize contract
-        client.initialize(&admin, &platform, &Some(250));
-        
-        // Send a tip
-        let receipt = client.tip_article(
-            &tipper,
-            &symbol_short!("article1"),
-            &author,
-            &1_000_000, // 0.1 XLM
+        let (token, _) = create_token(&env, &admin);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        assert_eq!(
+            client.get_ttl_config(),
+            TtlConfig { threshold: DEFAULT_TTL_THRESHOLD, extend_to: DEFAULT_TTL_EXTEND_TO }
         );
-        
-        // Verify receipt
-        assert_eq!(receipt.amount_sent, 1_000_000);
-        assert_eq!(receipt.platform_fee, 25_000); // 2.5%
-        assert_eq!(receipt.author_received, 975_000); // 97.5%
-        
-        // Check author balance
-        let balance = client.get_balance(&author);
-        assert_eq!(balance, 975_000);
-        
-        // Check tips for article
-        let tips = client.get_article_tips(&symbol_short!("article1"));
-        assert_eq!(tips.len(), 1);
+
+        assert_eq!(
+            client.try_set_ttl_config(&DAY_IN_LEDGERS, &DAY_IN_LEDGERS),
+            Err(Ok(TippingError::InvalidTtlConfig))
+        );
+        assert_eq!(
+            client.try_set_ttl_config(&DAY_IN_LEDGERS, &u32::MAX),
+            Err(Ok(TippingError::InvalidTtlConfig))
+        );
+
+        client.set_ttl_config(&DAY_IN_LEDGERS, &(60 * DAY_IN_LEDGERS));
+        assert_eq!(
+            client.get_ttl_config(),
+            TtlConfig { threshold: DAY_IN_LEDGERS, extend_to: 60 * DAY_IN_LEDGERS }
+        );
+
+        // Only extended once the remaining TTL falls below the new threshold
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 29 * DAY_IN_LEDGERS + 1);
+        client.bump_article(&symbol_short!("art1"));
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().instance().get_ttl(), 60 * DAY_IN_LEDGERS);
+        });
     }
-    
+
     #[test]
-    #[should_panic(expected = "Amount below minimum tip")]
-    fn test_minimum_tip_enforcement() {
+    fn test_tip_events() {
         let env = Env::default();
         env.mock_all_auths();
-        
+
         let contract_id = env.register(TippingContract, ());
         let client = TippingContractClient::new(&env, &contract_id);
-        
+
         let admin = Address::generate(&env);
         let platform = Address::generate(&env);
         let tipper = Address::generate(&env);
         let author = Address::generate(&env);
-        
-        client.initialize(&admin, &platform, &Some(250));
-        
-        // Try to send tip below minimum
-        client.tip_article(
-            &tipper,
-            &symbol_short!("article1"),
-            &author,
-            &50_000, // Below minimum
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&tipper, &10_000_000);
+        let asset_name = token::TokenClient::new(&env, &token).name();
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        let initialized = InitializedEvent {
+            admin: admin.clone(),
+            platform_address: platform.clone(),
+            native_token: token.clone(),
+            fee_bps: 250,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), initialized.topics(&env), initialized.data(&env))]
+        );
+
+        let article_id = symbol_short!("art1");
+        client.register_article(&author, &article_id);
+        let registered = ArticleRegisteredEvent { article_id: article_id.clone(), author: author.clone() };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), registered.topics(&env), registered.data(&env))]
+        );
+
+        // Token transfers come first, then the tip itself
+        let receipt = client.tip_article(&tipper, &article_id, &token, &1_000_000);
+        let tip = TipEvent {
+            article_id: article_id.clone(),
+            tip_id: receipt.tip_id,
+            highlight_id: None,
+            token: token.clone(),
+            tipper: tipper.clone(),
+            recipient: author.clone(),
+            gross: 1_000_000,
+            fee: 25_000,
+            net: 975_000,
+            payouts: vec![&env, RecipientPayout { recipient: author.clone(), amount: 975_000 }],
+            message: None,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    token.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), author.clone(), asset_name.clone()).into_val(&env),
+                    975_000i128.into_val(&env),
+                ),
+                (
+                    token.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), contract_id.clone(), asset_name.clone()).into_val(&env),
+                    25_000i128.into_val(&env),
+                ),
+                (contract_id.clone(), tip.topics(&env), tip.data(&env)),
+            ]
+        );
+
+        let highlight_id = String::from_str(&env, "highlight_1");
+        let receipt = client.tip_highlight_direct(&tipper, &highlight_id, &article_id, &token, &200_000);
+        let tip = TipEvent {
+            article_id,
+            tip_id: receipt.tip_id,
+            highlight_id: Some(highlight_id),
+            token: token.clone(),
+            tipper: tipper.clone(),
+            recipient: author.clone(),
+            gross: 200_000,
+            fee: 5_000,
+            net: 195_000,
+            payouts: vec![&env, RecipientPayout { recipient: author.clone(), amount: 195_000 }],
+            message: None,
+        };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    token.clone(),
+                    (symbol_short!("transfer"), tipper.clone(), author, asset_name.clone()).into_val(&env),
+                    195_000i128.into_val(&env),
+                ),
+                (
+                    token,
+                    (symbol_short!("transfer"), tipper, contract_id.clone(), asset_name).into_val(&env),
+                    5_000i128.into_val(&env),
+                ),
+                (contract_id, tip.topics(&env), tip.data(&env)),
+            ]
         );
     }
-    
+
     #[test]
-    fn test_tip_with_immediate_transfers() {
+    fn test_config_events() {
         let env = Env::default();
         env.mock_all_auths();
-        
+
         let contract_id = env.register(TippingContract, ());
         let client = TippingContractClient::new(&env, &contract_id);
-        
+
         let admin = Address::generate(&env);
         let platform = Address::generate(&env);
-        let tipper = Address::generate(&env);
-        let author = Address::generate(&env);
-        
-        client.initialize(&admin, &platform, &Some(250));
-        
-        // Send tips
-        client.tip_article(&tipper, &symbol_short!("art1"), &author, &1_000_000);
-        client.tip_article(&tipper, &symbol_short!("art2"), &author, &2_000_000);
-        
-        // Note: With mock_all_auths(), token transfers are simulated
-        // On testnet, the XLM token contract is pre-deployed by Stellar
-        // and these would be real XLM transfers
-        
-        // Check article total tips (for NFT threshold tracking)
-        let art1_total = client.get_article_total_tips(&symbol_short!("art1"));
-        assert_eq!(art1_total, 1_000_000);
-        
-        let art2_total = client.get_article_total_tips(&symbol_short!("art2"));
-        assert_eq!(art2_total, 2_000_000);
+        let (token, _) = create_token(&env, &admin);
+        let (usdc, _) = create_token(&env, &admin);
+
+        client.initialize(&admin, &platform, &token, &Some(250));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "pauser"));
+        client.grant_role(&admin, &admin, &Symbol::new(&env, "fee_manager"));
+
+        let effective_ledger = env.ledger().sequence() + 7 * DAY_IN_LEDGERS;
+        client.propose_fee_change(&admin, &300, &effective_ledger);
+        let event = FeeChangeProposedEvent { old_fee_bps: 250, new_fee_bps: 300, effective_ledger };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        env.ledger().set_sequence_number(effective_ledger);
+        client.activate_fee_change();
+        let event = FeeUpdatedEvent { old_fee_bps: 250, new_fee_bps: 300 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        client.add_allowed_token(&usdc, &10_000);
+        let event = TokenAllowedEvent { token: usdc.clone(), min_amount: 10_000 };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        client.remove_allowed_token(&usdc);
+        let event = TokenRemovedEvent { token: usdc };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        client.set_registration_cosign(&true);
+        let event = RegistrationCosignEvent { required: true };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        client.set_ttl_config(&DAY_IN_LEDGERS, &(60 * DAY_IN_LEDGERS));
+        let event = TtlConfigUpdatedEvent { threshold: DAY_IN_LEDGERS, extend_to: 60 * DAY_IN_LEDGERS };
+        assert_eq!(
+            env.events().all(),
+            vec![&env, (contract_id.clone(), event.topics(&env), event.data(&env))]
+        );
+
+        // OZ Pausable publishes its own unversioned event before ours
+        client.pause(&admin);
+        let event = PauseChangedEvent { caller: admin.clone(), paused: true };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (contract_id.clone(), Paused {}.topics(&env), Paused {}.data(&env)),
+                (contract_id.clone(), event.topics(&env), event.data(&env)),
+            ]
+        );
+
+        client.unpause(&admin);
+        let event = PauseChangedEvent { caller: admin, paused: false };
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (contract_id.clone(), Unpaused {}.topics(&env), Unpaused {}.data(&env)),
+                (contract_id, event.topics(&env), event.data(&env)),
+            ]
+        );
     }
-}
\ No newline at end of file
+}
@@ -287,7 +287,7 @@ export class StellarClient {
           'tip_article',
           StellarSdk.nativeToScVal(tipperPublicKey, { type: 'address' }),
           StellarSdk.nativeToScVal(shortArticleId(params.articleId), { type: 'symbol' }), // hashed for collision resistance
-          StellarSdk.nativeToScVal(params.authorAddress, { type: 'address' }),
+          StellarSdk.nativeToScVal(this.nativeTokenId(), { type: 'address' }), // author is resolved on-chain from the article registry
           StellarSdk.nativeToScVal(stroopsBigInt, { type: 'i128' })
         )
       )
@@ -347,7 +347,7 @@ export class StellarClient {
           StellarSdk.nativeToScVal(tipperPublicKey, { type: 'address' }),
           StellarSdk.nativeToScVal(params.highlightId, { type: 'string' }),
           StellarSdk.nativeToScVal(shortArticleId(params.articleId), { type: 'symbol' }), // hashed for collision resistance
-          StellarSdk.nativeToScVal(params.authorAddress, { type: 'address' }),
+          StellarSdk.nativeToScVal(this.nativeTokenId(), { type: 'address' }), // author is resolved on-chain from the article registry
           StellarSdk.nativeToScVal(stroopsBigInt, { type: 'i128' })
         )
       )
@@ -365,6 +365,13 @@ export class StellarClient {
     }
   }
 
+  /**
+   * Stellar Asset Contract address of native XLM, the token tips are paid in
+   */
+  private nativeTokenId(): string {
+    return StellarSdk.Asset.native().contractId(this.networkPassphrase)
+  }
+
   /**
    * Submit signed transaction to network
    */
@@ -418,9 +425,10 @@ export class StellarClient {
   }
 
   /**
-   * Get article tips from smart contract
+   * Get a page of article tips from smart contract, oldest first
+   * The contract caps `limit` at 50 tips per call
    */
-  async getArticleTips(articleId: string): Promise<TipData[]> {
+  async getArticleTips(articleId: string, start = 0, limit = 50): Promise<TipData[]> {
     try {
       const contract = new StellarSdk.Contract(STELLAR_CONFIG.TIPPING_CONTRACT_ID)
 
@@ -432,7 +440,12 @@ export class StellarClient {
         networkPassphrase: this.networkPassphrase,
       })
         .addOperation(
-          contract.call('get_article_tips', StellarSdk.nativeToScVal(shortArticleId(articleId), { type: 'symbol' }))
+          contract.call(
+            'get_article_tips',
+            StellarSdk.nativeToScVal(shortArticleId(articleId), { type: 'symbol' }),
+            StellarSdk.nativeToScVal(start, { type: 'u32' }),
+            StellarSdk.nativeToScVal(limit, { type: 'u32' })
+          )
         )
         .setTimeout(30)
         .build()
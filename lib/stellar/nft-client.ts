@@ -141,7 +141,6 @@ export class NFTClient {
             'mint_article_nft',
             StellarSdk.nativeToScVal(params.authorAddress, { type: 'address' }), // author
             StellarSdk.nativeToScVal(params.articleId, { type: 'symbol' }), // article_id
-            StellarSdk.nativeToScVal(params.tipAmount, { type: 'i128' }), // tip_amount
             StellarSdk.nativeToScVal(params.metadataUrl, { type: 'string' }) // metadata_url
           )
         )
@@ -269,7 +268,7 @@ export class NFTClient {
         networkPassphrase: this.networkPassphrase,
       })
         .addOperation(
-          contract.call('get_owner', StellarSdk.nativeToScVal(tokenId, { type: 'u64' }))
+          contract.call('owner_of', StellarSdk.nativeToScVal(tokenId, { type: 'u64' }))
         )
         .setTimeout(30)
         .build()